@@ -1,30 +1,171 @@
-use crate::cli::{Cli, Command};
+use crate::cli::{Cli, Command, GroupAction, JobSelector, ListFormat};
 use crate::config;
 use crate::daemon;
-use crate::model::DaemonState;
-use crate::paths::AppPaths;
+use crate::error::MacrondError;
+use crate::launchd;
+use crate::logging;
+use crate::model::{ExecutionRecord, ScheduleConfig};
+use crate::paths::{self, AppPaths};
 use crate::scheduler;
+use crate::top;
 use crate::tui;
 use anyhow::{Context, Result, anyhow, bail};
-use chrono::Local;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use clap::ValueEnum;
+use regex::Regex;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
 
 pub async fn run(cli: Cli) -> Result<()> {
-    let paths = AppPaths::new(&cli.base_dir)?;
+    if let Some(Command::Profiles) = cli.command {
+        return profiles();
+    }
+    let config_file = cli
+        .config
+        .or_else(|| std::env::var_os("MACROND_CONFIG").filter(|v| !v.is_empty()).map(PathBuf::from));
+    let paths = AppPaths::resolve(cli.base_dir.as_deref(), cli.profile.as_deref())?
+        .with_config_file(config_file.as_deref())?;
     paths.ensure_dirs()?;
+    logging::set_level(resolve_log_level(cli.log_level, cli.verbose)?);
+    daemon::set_history_size(resolve_history_size(cli.history_size)?);
+    config::set_verify_checksums(cli.verify_jobs || env_flag("MACROND_VERIFY_JOBS"));
+    paths::set_file_mode(resolve_file_mode(cli.file_mode.as_deref())?);
+    // Interval schedules count from the daemon's recorded start, or from now if it never ran.
+    let anchor = daemon::read_state(&paths).ok().and_then(|s| s.daemon_started_at);
+    scheduler::set_start_anchor(anchor.unwrap_or_else(Local::now));
 
-    match cli.command.unwrap_or(Command::Tui) {
+    match cli.command.unwrap_or(Command::Tui {
+        plain: false,
+        read_only: false,
+    }) {
         Command::Version => version(),
-        Command::Start => start(&paths),
-        Command::Stop => stop(&paths),
+        Command::Profiles => profiles(),
+        Command::Start { state_interval, tick_ms } => start(&paths, state_interval, tick_ms),
+        Command::Stop { signal, wait, timeout } => {
+            stop(&paths, &signal, wait.then_some(Duration::from_secs(timeout)))
+        }
+        Command::Restart {
+            state_interval,
+            tick_ms,
+            signal,
+            timeout,
+        } => {
+            stop(&paths, &signal, Some(Duration::from_secs(timeout)))?;
+            start(&paths, state_interval, tick_ms)
+        }
         Command::Status => status(&paths),
-        Command::List => list(&paths),
-        Command::Logs { job, tail } => logs(&paths, job.as_deref(), tail),
-        Command::Run { job_id } => run_job(&paths, &job_id).await,
-        Command::Tui => tui::run_tui(&paths),
-        Command::Daemon => daemon::run_daemon(paths).await,
+        Command::Pause { block_manual } => pause(&paths, block_manual),
+        Command::Resume => resume(&paths),
+        Command::List { format } => list(&paths, format),
+        Command::Validate => validate(&paths),
+        Command::Seal => seal(&paths),
+        Command::Reload { check } => reload(&paths, check),
+        Command::Logs {
+            job,
+            pattern,
+            run_id,
+            grep,
+            failures,
+            tail,
+            head,
+            since,
+            until,
+            follow,
+            json,
+            all_files,
+        } => {
+            let job = match pattern {
+                Some(pattern) => Some(match_job_id(&paths, &pattern)?),
+                None => job,
+            };
+            let filter = LogFilter {
+                job_id: job.as_deref(),
+                run_id: run_id.as_deref(),
+                grep: grep
+                    .as_deref()
+                    .map(Regex::new)
+                    .transpose()
+                    .context("invalid --grep pattern")?,
+                failures,
+                since: since.as_deref().map(parse_log_time).transpose()?,
+                until: until.as_deref().map(parse_log_time).transpose()?,
+            };
+            logs(&paths, &filter, tail, head, follow, json, all_files)
+        }
+        Command::Run {
+            job_id,
+            tag,
+            pattern,
+            inline,
+            queue,
+            show_output,
+            wait,
+        } => {
+            let mode = RunMode::from_flags(inline, queue);
+            let job_id = match pattern {
+                Some(pattern) => Some(match_job_id(&paths, &pattern)?),
+                None => job_id,
+            };
+            match job_id {
+                Some(job_id) if wait => run_job_and_wait(&paths, &job_id, mode, show_output).await,
+                Some(job_id) => run_job(&paths, &job_id, mode, show_output).await,
+                None => run_batch(&paths, tag.as_deref(), mode, show_output).await,
+            }
+        }
+        Command::RunAll { inline, queue } => {
+            run_batch(&paths, None, RunMode::from_flags(inline, queue), false).await
+        }
+        Command::Trigger { job } => run_job(&paths, &selected_job_id(&paths, job)?, RunMode::Queue, false).await,
+        Command::Edit { job } => edit(&paths, &selected_job_id(&paths, job)?),
+        Command::Show { job, explain } => show(&paths, &selected_job_id(&paths, job)?, explain),
+        Command::Next { job, count, until } => next(
+            &paths,
+            &selected_job_id(&paths, job)?,
+            count,
+            until.as_deref().map(parse_log_time).transpose()?,
+        ),
+        Command::Why { job } => why(&paths, &selected_job_id(&paths, job)?),
+        Command::Group { action } => match action {
+            GroupAction::List => list_groups(&paths),
+            GroupAction::Enable { name } => set_group_enabled(&paths, &name, true),
+            GroupAction::Disable { name } => set_group_enabled(&paths, &name, false),
+        },
+        Command::Top => top::run_top(&paths),
+        Command::Tui { plain, read_only } => tui::run_tui(&paths, plain, read_only || paths.config_file.is_some()),
+        Command::InstallAgent { name } => launchd::install_agent(&paths, name.as_deref()),
+        Command::UninstallAgent { name } => launchd::uninstall_agent(&paths, name.as_deref()),
+        Command::Daemon {
+            state_interval,
+            tick_ms,
+            once,
+        } => daemon::run_daemon(paths, state_interval, tick_ms, once).await,
+    }
+}
+
+fn selected_job_id(paths: &AppPaths, selector: JobSelector) -> Result<String> {
+    match (selector.job_id, selector.pattern) {
+        (Some(job_id), _) => Ok(job_id),
+        (None, Some(pattern)) => match_job_id(paths, &pattern),
+        (None, None) => bail!("pass a job id or --match"),
+    }
+}
+
+fn match_job_id(paths: &AppPaths, pattern: &str) -> Result<String> {
+    let loaded = config::load_jobs_skipping_collisions(paths)?;
+    let matches = config::match_jobs(&loaded.jobs, pattern);
+    match matches.as_slice() {
+        [job] => Ok(job.id.clone()),
+        [] => Err(MacrondError::JobNotFound(format!("no job id or name matches '{pattern}'")).into()),
+        _ => Err(MacrondError::AmbiguousJob {
+            pattern: pattern.to_string(),
+            candidates: matches.iter().map(|j| format!("{}  ({})", j.id, j.name)).collect(),
+        }
+        .into()),
     }
 }
 
@@ -33,17 +174,110 @@ fn version() -> Result<()> {
     Ok(())
 }
 
-fn start(paths: &AppPaths) -> Result<()> {
+fn profiles() -> Result<()> {
+    let dir = paths::profiles_dir()?;
+    let mut names: Vec<String> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .collect(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(err).with_context(|| format!("read {}", dir.display())),
+    };
+    names.sort();
+    if names.is_empty() {
+        println!("no profiles in {} (create one with --profile <name>)", dir.display());
+        return Ok(());
+    }
+    for name in names {
+        let profile = AppPaths::new(dir.join(&name))?;
+        let jobs = config::load_jobs(&profile).map(|jobs| jobs.len().to_string());
+        let daemon = match daemon::daemon_running(&profile)? {
+            Some(pid) => format!("running pid={pid}"),
+            None => "stopped".to_string(),
+        };
+        println!(
+            "{name}\tjobs={}\tdaemon={daemon}\t{}",
+            jobs.unwrap_or_else(|_| "?".to_string()),
+            profile.base_dir.display()
+        );
+    }
+    Ok(())
+}
+
+fn resolve_history_size(flag: Option<usize>) -> Result<usize> {
+    let (size, source) = match flag {
+        Some(size) => (size, "--history-size".to_string()),
+        None => match std::env::var("MACROND_HISTORY_SIZE").ok().filter(|v| !v.trim().is_empty()) {
+            Some(value) => (
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| anyhow!("invalid MACROND_HISTORY_SIZE '{value}', expected a number"))?,
+                "MACROND_HISTORY_SIZE".to_string(),
+            ),
+            None => return Ok(daemon::DEFAULT_HISTORY_SIZE),
+        },
+    };
+    if !(1..=daemon::MAX_HISTORY_SIZE).contains(&size) {
+        bail!("{source} must be 1..={}", daemon::MAX_HISTORY_SIZE);
+    }
+    Ok(size)
+}
+
+fn resolve_file_mode(flag: Option<&str>) -> Result<u32> {
+    if let Some(value) = flag {
+        return paths::parse_file_mode(value).context("--file-mode");
+    }
+    match std::env::var("MACROND_FILE_MODE").ok().filter(|v| !v.trim().is_empty()) {
+        Some(value) => paths::parse_file_mode(&value).context("MACROND_FILE_MODE"),
+        None => Ok(paths::DEFAULT_FILE_MODE),
+    }
+}
+
+fn env_flag(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|v| matches!(v.trim(), "1" | "true" | "yes"))
+}
+
+fn resolve_log_level(flag: Option<logging::LogLevel>, verbose: bool) -> Result<logging::LogLevel> {
+    if verbose {
+        return Ok(logging::LogLevel::Debug);
+    }
+    if let Some(level) = flag {
+        return Ok(level);
+    }
+    match std::env::var("MACROND_LOG_LEVEL").ok().filter(|v| !v.trim().is_empty()) {
+        Some(value) => logging::LogLevel::from_str(value.trim(), true)
+            .map_err(|_| anyhow!("invalid MACROND_LOG_LEVEL '{value}', expected debug, info, warn or error")),
+        None => Ok(logging::LogLevel::Info),
+    }
+}
+
+fn start(paths: &AppPaths, state_interval: u64, tick_ms: u64) -> Result<()> {
     if let Some(pid) = daemon::daemon_running(paths)? {
         println!("daemon is already running (pid={pid})");
         return Ok(());
     }
+    paths.check_writable()?;
 
     let exe = std::env::current_exe().context("resolve current exe")?;
     let child = std::process::Command::new(exe)
         .arg("--base-dir")
         .arg(&paths.base_dir)
         .arg("daemon")
+        .arg("--state-interval")
+        .arg(state_interval.to_string())
+        .arg("--tick-ms")
+        .arg(tick_ms.to_string())
+        .arg("--log-level")
+        .arg(logging::level().as_str())
+        .arg("--history-size")
+        .arg(daemon::history_size().to_string())
+        .args(config::verify_checksums().then_some("--verify-jobs"))
+        .arg("--file-mode")
+        .arg(format!("{:04o}", paths::file_mode()))
+        .args(paths.config_args())
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -54,34 +288,106 @@ fn start(paths: &AppPaths) -> Result<()> {
     Ok(())
 }
 
-fn stop(paths: &AppPaths) -> Result<()> {
+fn stop(paths: &AppPaths, signal: &str, wait: Option<Duration>) -> Result<()> {
+    let signal = parse_signal(signal)?;
     let Some(pid) = daemon::daemon_running(paths)? else {
         println!("daemon is not running");
         return Ok(());
     };
 
-    nix::sys::signal::kill(
-        nix::unistd::Pid::from_raw(pid),
-        Some(nix::sys::signal::Signal::SIGINT),
-    )
-    .context("failed to send SIGINT")?;
-    println!("stop signal sent to pid={pid}");
+    nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid), Some(signal))
+        .with_context(|| format!("failed to send {signal}"))?;
+    println!("stop signal {signal} sent to pid={pid}");
+
+    if let Some(timeout) = wait {
+        let deadline = Instant::now() + timeout;
+        while daemon::is_pid_running(pid) {
+            if Instant::now() >= deadline {
+                bail!("daemon pid={pid} still running {}s after {signal}", timeout.as_secs());
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        println!("daemon stopped (pid={pid})");
+    }
     Ok(())
 }
 
+fn parse_signal(value: &str) -> Result<nix::sys::signal::Signal> {
+    let name = value.trim().to_ascii_uppercase();
+    let name = if name.starts_with("SIG") { name } else { format!("SIG{name}") };
+    name.parse()
+        .map_err(|_| anyhow!("unknown signal '{value}', expected a name like INT, TERM or KILL"))
+}
+
 fn status(paths: &AppPaths) -> Result<()> {
-    if let Some(pid) = daemon::daemon_running(paths)? {
+    let running = daemon::daemon_running(paths)?;
+    if let Some(pid) = running {
         println!("daemon: running (pid={pid})");
     } else {
         println!("daemon: stopped");
     }
+    let running = running.is_some();
 
     if paths.state_file.exists() {
-        let state = read_state(paths)?;
-        println!("updated_at: {}", state.updated_at.format("%Y-%m-%d %H:%M:%S"));
-        println!("loaded_jobs: {}", state.jobs.len());
+        let state = daemon::read_state(paths)?;
+        let age = Local::now() - state.updated_at;
+        println!(
+            "updated_at: {} (age={}s)",
+            state.updated_at.format("%Y-%m-%d %H:%M:%S"),
+            age.num_seconds()
+        );
+        let stale = running
+            && state.running
+            && daemon::state_stale_after(&state).is_some_and(|limit| age > limit);
+        if stale {
+            println!("warning: state not updated for {}s, daemon loop may be stuck", age.num_seconds());
+        }
+        let enabled = state.jobs.iter().filter(|j| j.enabled).count();
+        println!(
+            "loaded_jobs: {} (enabled={} disabled={})",
+            state.jobs.len(),
+            enabled,
+            state.jobs.len() - enabled
+        );
+
+        let since = Local::now() - chrono::TimeDelta::hours(24);
+        let recent: Vec<_> = state.recent_runs.iter().filter(|r| r.ended_at >= since).collect();
+        let count = |status: &str| recent.iter().filter(|r| r.status == status).count();
+        println!(
+            "runs_24h: {} (success={} failed={} timeout={})",
+            recent.len(),
+            count("success"),
+            count("failed"),
+            count("timeout")
+        );
+
+        match state
+            .jobs
+            .iter()
+            .filter_map(|j| j.next_run.map(|t| (t, &j.id)))
+            .min()
+        {
+            Some((next, id)) => println!("next_run: {} ({id})", next.format("%Y-%m-%d %H:%M:%S")),
+            None => println!("next_run: -"),
+        }
+        if state.paused {
+            if state.block_manual {
+                println!("paused: yes (manual runs blocked)");
+            } else {
+                println!("paused: yes");
+            }
+        }
+        for job in &state.jobs {
+            if let Some(stats) = &job.duration {
+                println!("avg_duration: {} {:.1}s (runs={})", job.id, stats.ema_seconds, stats.samples);
+            }
+        }
         if let Some(err) = state.last_reload_error {
             println!("last_reload_error: {err}");
+            bail!("job config reload failed, see last_reload_error");
+        }
+        if stale {
+            bail!("daemon state is stale");
         }
     } else {
         println!("state: unavailable");
@@ -90,51 +396,604 @@ fn status(paths: &AppPaths) -> Result<()> {
     Ok(())
 }
 
-fn list(paths: &AppPaths) -> Result<()> {
-    if paths.state_file.exists() {
-        let state = read_state(paths)?;
-        if state.jobs.is_empty() {
-            println!("no jobs loaded");
-            return Ok(());
+fn pause(paths: &AppPaths, block_manual: bool) -> Result<()> {
+    if daemon::daemon_running(paths)?.is_none() {
+        println!("daemon is not running");
+        return Ok(());
+    }
+    daemon::submit_pause_request(paths, block_manual)?;
+    println!("pause request submitted (block_manual={block_manual})");
+    Ok(())
+}
+
+fn resume(paths: &AppPaths) -> Result<()> {
+    if daemon::daemon_running(paths)?.is_none() {
+        println!("daemon is not running");
+        return Ok(());
+    }
+    daemon::submit_resume_request(paths)?;
+    println!("resume request submitted");
+    Ok(())
+}
+
+fn validate(paths: &AppPaths) -> Result<()> {
+    let loaded = config::load_jobs_skipping_collisions(paths)?;
+    let now = Local::now();
+    let mut warnings = 0;
+    let mut errors = 0;
+    for collision in &loaded.collisions {
+        println!("error: {collision}");
+        errors += 1;
+    }
+    for rejected in &loaded.rejected {
+        println!("error: {rejected}");
+        errors += 1;
+    }
+    let jobs = loaded.jobs;
+    for job in &jobs {
+        if let Err(err) = config::check_job_working_dirs(&paths.base_dir, job) {
+            println!("error: job={} {err:#}", job.id);
+            errors += 1;
         }
-        for job in state.jobs {
-            let next = job
-                .next_run
-                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
-                .unwrap_or_else(|| "-".to_string());
-            let last = job
-                .last_result
-                .as_ref()
-                .map(|r| format!("{}({})", r.status, r.ended_at.format("%m-%d %H:%M:%S")))
-                .unwrap_or_else(|| "-".to_string());
-            println!(
-                "id={} enabled={} schedule={} next_run={} last={}",
-                job.id, job.enabled, job.schedule, next, last
-            );
+        for warning in config::schedule_warnings(job, now) {
+            println!("warning: job={} {warning}", job.id);
+            warnings += 1;
         }
+    }
+    if errors > 0 {
+        bail!("{errors} job(s) have errors");
+    }
+    println!("ok: jobs={} warnings={warnings}", jobs.len());
+    Ok(())
+}
+
+fn reload(paths: &AppPaths, check: bool) -> Result<()> {
+    let loaded = config::load_jobs_skipping_collisions(paths)
+        .context("config does not load, daemon not signaled")?;
+    for collision in &loaded.collisions {
+        println!("warning: {collision}");
+    }
+    for rejected in &loaded.rejected {
+        println!("warning: {rejected}");
+    }
+    println!(
+        "ok: jobs={} collisions={} checksum_mismatches={}",
+        loaded.jobs.len(),
+        loaded.collisions.len(),
+        loaded.rejected.len()
+    );
+    if check {
         return Ok(());
     }
 
-    let jobs = config::load_jobs(&paths.jobs_dir)?;
-    if jobs.is_empty() {
-        println!("no jobs found in jobs/");
+    let Some(pid) = daemon::daemon_running(paths)? else {
+        println!("daemon is not running, jobs will be loaded on next start");
+        return Ok(());
+    };
+    let signal = nix::sys::signal::Signal::SIGHUP;
+    nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid), Some(signal))
+        .with_context(|| format!("failed to send {signal}"))?;
+    println!("reload signal {signal} sent to pid={pid}");
+    Ok(())
+}
+
+fn seal(paths: &AppPaths) -> Result<()> {
+    let sealed = match &paths.config_file {
+        Some(file) => config::seal_config_file(file)?,
+        None => config::seal_job_files(&paths.jobs_dir, &paths.defaults_file)?,
+    };
+    println!("sealed: {} job file(s)", sealed.len());
+    Ok(())
+}
+
+fn list_groups(paths: &AppPaths) -> Result<()> {
+    let jobs = config::load_jobs_skipping_collisions(paths)?.jobs;
+    let mut groups: std::collections::BTreeMap<&str, Vec<&crate::model::JobConfig>> = Default::default();
+    for job in &jobs {
+        if let Some(group) = &job.group {
+            groups.entry(group).or_default().push(job);
+        }
+    }
+    if groups.is_empty() {
+        println!("no groups defined");
         return Ok(());
     }
+    for (name, members) in groups {
+        let ids: Vec<&str> = members.iter().map(|j| j.id.as_str()).collect();
+        println!(
+            "group={name} jobs={} enabled={} members={}",
+            members.len(),
+            members.iter().filter(|j| j.enabled).count(),
+            ids.join(",")
+        );
+    }
+    Ok(())
+}
+
+fn set_group_enabled(paths: &AppPaths, group: &str, enabled: bool) -> Result<()> {
+    paths.check_jobs_writable()?;
+    let jobs = config::load_jobs_skipping_collisions(paths)?.jobs;
+    let config::GroupUpdate { changed, failed } = config::set_group_enabled(&paths.jobs_dir, &jobs, group, enabled)?;
+    let action = if enabled { "enabled" } else { "disabled" };
+    println!("group={group} {action}={}", changed.len());
+    for id in &changed {
+        println!("  {action} {id}");
+    }
+    for (id, err) in &failed {
+        eprintln!("  failed {id}: {err:#}");
+    }
+    if !failed.is_empty() {
+        bail!("{} job(s) in group '{group}' could not be updated", failed.len());
+    }
+    Ok(())
+}
+
+fn show(paths: &AppPaths, job_id: &str, explain: bool) -> Result<()> {
+    let jobs = config::load_jobs_skipping_collisions(paths)?.jobs;
+    let mut job = jobs
+        .into_iter()
+        .find(|j| j.id == job_id)
+        .ok_or_else(|| MacrondError::JobNotFound(job_id.to_string()))?;
+    if let Err(err) = config::check_job_working_dirs(&paths.base_dir, &job) {
+        eprintln!("warning: {err:#}");
+    }
+    job.command = daemon::resolve_command(&paths.base_dir, &job.command)?;
+    for hook in [&mut job.on_success, &mut job.on_failure, &mut job.on_slow].into_iter().flatten() {
+        *hook = daemon::resolve_command(&paths.base_dir, hook)?;
+    }
+    println!("{}", serde_json::to_string_pretty(&job)?);
+
+    let now = Local::now();
+    let zone = std::env::var("TZ").unwrap_or_else(|_| "system".to_string());
+    println!("timezone: {zone} (UTC{})", now.format("%:z"));
+    println!("schedule: {}", scheduler::schedule_label(&job));
+    if explain && let ScheduleConfig::Cron { expression } = &job.schedule {
+        println!("explain: {}", scheduler::describe_cron(expression)?);
+    }
+    println!("next_runs:");
+    let mut after = now;
+    for idx in 0..3 {
+        match scheduler::next_run_after(&job, after)? {
+            Some(next) => {
+                println!("  {}", next.format("%Y-%m-%d %H:%M:%S%:z"));
+                after = next;
+            }
+            None => {
+                if idx == 0 {
+                    println!("  -");
+                }
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+const NEXT_DEFAULT_COUNT: usize = 5;
+// Keeps `next --until` on an every-minute schedule from printing for ages.
+const NEXT_MAX_COUNT: usize = 10_000;
+
+fn next(paths: &AppPaths, job_id: &str, count: Option<usize>, until: Option<DateTime<Local>>) -> Result<()> {
+    let jobs = config::load_jobs_skipping_collisions(paths)?.jobs;
+    let job = jobs
+        .into_iter()
+        .find(|j| j.id == job_id)
+        .ok_or_else(|| MacrondError::JobNotFound(job_id.to_string()))?;
+    let default_count = if until.is_some() { NEXT_MAX_COUNT } else { NEXT_DEFAULT_COUNT };
+    let limit = count.unwrap_or(default_count).min(NEXT_MAX_COUNT);
+
+    let mut after = Local::now();
+    let mut printed = 0;
+    while let Some(next) = scheduler::next_run_after(&job, after)? {
+        if until.is_some_and(|until| next > until) {
+            break;
+        }
+        if printed == limit {
+            if limit == NEXT_MAX_COUNT {
+                println!("... stopped after {NEXT_MAX_COUNT} runs, use an earlier --until");
+            }
+            break;
+        }
+        println!("{}", next.format("%Y-%m-%d %H:%M:%S%:z"));
+        printed += 1;
+        after = next;
+    }
+    if printed == 0 {
+        println!("no runs{}", until.map(|u| format!(" until {}", u.format("%Y-%m-%d %H:%M:%S%:z"))).unwrap_or_default());
+    }
+    Ok(())
+}
+
+// Collects the usual "enabled but never ran" checks into one report, ending with the
+// reasons that would keep the job from being scheduled.
+fn why(paths: &AppPaths, job_id: &str) -> Result<()> {
+    let mut reasons = Vec::new();
     let now = Local::now();
-    for job in jobs {
-        let next = scheduler::next_run_after(&job, now)?.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string());
+
+    let pid = daemon::daemon_running(paths)?;
+    let state = daemon::read_state(paths).ok();
+    match pid {
+        Some(pid) => println!("daemon: running (pid={pid})"),
+        None => {
+            println!("daemon: stopped");
+            reasons.push("daemon is not running, start it with `macrond start`".to_string());
+        }
+    }
+    if pid.is_some() && let Some(state) = state.as_ref().filter(|s| s.paused) {
+        println!("paused: yes{}", if state.block_manual { " (manual runs blocked)" } else { "" });
+        reasons.push("daemon is paused, scheduled runs are skipped until `macrond resume`".to_string());
+    }
+
+    let file = config::job_file_path(&paths.jobs_dir, job_id);
+    // With --config the file loads as a whole: no per-job file to fall back to, and no
+    // collisions or checksum rejections to attribute to this job.
+    let (job, load_error) = if let Some(config_file) = &paths.config_file {
+        match config::load_config_file(config_file) {
+            Ok(jobs) => (jobs.into_iter().find(|j| j.id == job_id), None),
+            Err(err) => {
+                println!("config: invalid: {err:#}");
+                reasons.push(format!(
+                    "{} is invalid, the daemon keeps its previous jobs until it is fixed",
+                    config_file.display()
+                ));
+                print_reasons(&reasons);
+                return Ok(());
+            }
+        }
+    } else {
+        match config::load_jobs_skipping_collisions(paths) {
+            Ok(loaded) => {
+                for collision in loaded.collisions.iter().filter(|c| c.id == job_id) {
+                    println!("collision: {collision}");
+                    reasons.push(format!("{} is skipped because it reuses id {job_id}", collision.skipped.display()));
+                }
+                if let Some(rejected) = loaded.rejected.iter().find(|r| r.path == file) {
+                    println!("integrity: {rejected}");
+                    reasons.push(format!("{} is skipped because it does not match its checksum", file.display()));
+                }
+                (loaded.jobs.into_iter().find(|j| j.id == job_id), None)
+            }
+            Err(err) => (None, Some(err)),
+        }
+    };
+    let job = match job {
+        Some(job) => job,
+        None if paths.config_file.is_none() && file.exists() => {
+            let defaults = config::load_defaults(&paths.defaults_file)?;
+            match config::load_job_file(&file, defaults.as_ref()) {
+                Ok(job) => job,
+                Err(err) => {
+                    println!("config: invalid: {err:#}");
+                    reasons.push(format!("{} is invalid", file.display()));
+                    print_reasons(&reasons);
+                    return Ok(());
+                }
+            }
+        }
+        None => match load_error {
+            Some(err) => return Err(anyhow::Error::new(err).context(format!("job not found: {job_id}"))),
+            None => return Err(MacrondError::JobNotFound(job_id.to_string()).into()),
+        },
+    };
+    if let Some(err) = load_error {
+        println!("config: jobs failed to load: {err:#}");
+        reasons.push("jobs dir does not load, the daemon keeps its previous config until it is fixed".to_string());
+    }
+
+    println!("enabled: {}", job.enabled);
+    if let Some(at) = job.completed_at.filter(|_| !job.enabled) {
+        reasons.push(format!("one-shot job already ran at {}, it was disabled after completing", at.format("%Y-%m-%d %H:%M:%S")));
+    } else if !job.enabled {
+        reasons.push("job is disabled".to_string());
+    }
+    if job.manual_only {
+        println!("manual_only: true");
+        reasons.push("job is manual_only and never scheduled".to_string());
+    }
+    println!("schedule: {}", scheduler::schedule_label(&job));
+    if job.active_from.is_some() || job.active_until.is_some() {
         println!(
-            "id={} enabled={} schedule={} next_run={}",
-            job.id,
-            job.enabled,
-            scheduler::schedule_label(&job),
-            next.unwrap_or_else(|| "-".to_string())
+            "active_window: {} .. {}",
+            job.active_from.as_deref().unwrap_or("-"),
+            job.active_until.as_deref().unwrap_or("-")
         );
+        if !scheduler::in_active_window(&job, now) {
+            reasons.push("now is outside the job's active_from/active_until window".to_string());
+        }
+    }
+    match scheduler::next_run_after(&job, now) {
+        Ok(Some(next)) => println!("next_run: {}", next.format("%Y-%m-%d %H:%M:%S%:z")),
+        Ok(None) => {
+            println!("next_run: -");
+            if job.enabled && !job.manual_only {
+                reasons.push("schedule has no future run".to_string());
+            }
+        }
+        Err(err) => {
+            println!("next_run: error: {err:#}");
+            reasons.push("schedule cannot be evaluated".to_string());
+        }
+    }
+    for warning in config::schedule_warnings(&job, now) {
+        println!("warning: {warning}");
+    }
+
+    if let Some(state) = &state {
+        match state.jobs.iter().find(|j| j.id == job_id) {
+            Some(view) => {
+                let next = view.next_run.map(|t| t.format("%Y-%m-%d %H:%M:%S%:z").to_string());
+                println!("daemon_next_run: {}", next.as_deref().unwrap_or("-"));
+                match &view.last_result {
+                    Some(last) => println!(
+                        "last_run: {} status={} trigger={}",
+                        last.started_at.format("%Y-%m-%d %H:%M:%S"),
+                        last.status,
+                        last.trigger
+                    ),
+                    None => println!("last_run: -"),
+                }
+            }
+            None => {
+                println!("daemon_next_run: job not loaded by daemon");
+                if pid.is_some() {
+                    reasons.push("daemon has not loaded this job, check last_reload_error".to_string());
+                }
+            }
+        }
+        if let Some(err) = &state.last_reload_error {
+            let source = paths.config_file.as_ref().unwrap_or(&file);
+            let name = source.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if err.contains(job_id) || err.contains(&name) {
+                println!("last_reload_error: {err}");
+                reasons.push("last daemon reload reported an error for this job".to_string());
+            }
+        }
+    }
+
+    if job.allow_overlap {
+        println!("running: not tracked (allow_overlap=true)");
+    } else if daemon::job_lock_held(paths, &job)? {
+        println!("running: yes");
+        reasons.push("a previous run still holds the lock, new runs are skipped (allow_overlap=false)".to_string());
+    } else {
+        println!("running: no");
+    }
+
+    print_reasons(&reasons);
+    Ok(())
+}
+
+fn print_reasons(reasons: &[String]) {
+    if reasons.is_empty() {
+        println!("reasons: none found, the job should run at next_run");
+        return;
+    }
+    println!("reasons:");
+    for reason in reasons {
+        println!("  - {reason}");
+    }
+}
+
+fn edit(paths: &AppPaths, job_id: &str) -> Result<()> {
+    paths.check_jobs_writable()?;
+    let path = config::job_file_path(&paths.jobs_dir, job_id);
+    let backup = std::fs::read(&path).with_context(|| format!("job file not found: {}", path.display()))?;
+
+    let status = match std::env::var("EDITOR").ok().filter(|e| !e.trim().is_empty()) {
+        Some(editor) => {
+            let mut parts = shlex::split(&editor).ok_or_else(|| anyhow!("cannot parse EDITOR '{editor}'"))?;
+            let program = parts.remove(0);
+            std::process::Command::new(&program)
+                .args(parts)
+                .arg(&path)
+                .status()
+                .with_context(|| format!("failed to run editor {program}"))?
+        }
+        None => match std::process::Command::new("vi").arg(&path).status() {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => std::process::Command::new("nano")
+                .arg(&path)
+                .status()
+                .context("EDITOR is not set and neither vi nor nano is available")?,
+            other => other.context("failed to run vi")?,
+        },
+    };
+    if !status.success() {
+        paths::write_file(&path, &backup)?;
+        bail!("editor exited with {status}, changes discarded");
     }
+
+    if std::fs::read(&path)? == backup {
+        println!("no changes: {}", path.display());
+        return Ok(());
+    }
+    let defaults = config::load_defaults(&paths.defaults_file)?;
+    let checked = config::load_job_file(&path, defaults.as_ref()).map_err(anyhow::Error::from).and_then(|job| {
+        if job.id != job_id {
+            bail!("job id changed from {job_id} to {}, rename the file instead", job.id);
+        }
+        Ok(())
+    });
+    if let Err(err) = checked {
+        paths::write_file(&path, &backup)?;
+        bail!("invalid job file, original restored: {err:#}");
+    }
+    config::update_checksum(&path)?;
+    println!("saved: {}", path.display());
     Ok(())
 }
 
-fn logs(paths: &AppPaths, job_id: Option<&str>, tail: usize) -> Result<()> {
+struct ListEntry {
+    id: String,
+    name: String,
+    enabled: bool,
+    schedule: String,
+    next_run: Option<String>,
+    last: Option<ExecutionRecord>,
+}
+
+fn list(paths: &AppPaths, format: ListFormat) -> Result<()> {
+    let from_state = paths.state_file.exists();
+    let entries: Vec<ListEntry> = if from_state {
+        let state = daemon::read_state(paths)?;
+        if state.jobs.is_empty() {
+            println!("no jobs loaded");
+            return Ok(());
+        }
+        state
+            .jobs
+            .into_iter()
+            .map(|job| ListEntry {
+                id: job.id,
+                name: job.name,
+                enabled: job.enabled,
+                schedule: job.schedule,
+                next_run: job.next_run.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()),
+                last: job.last_result,
+            })
+            .collect()
+    } else {
+        let jobs = config::load_jobs(paths)?;
+        if jobs.is_empty() {
+            println!("no jobs found in jobs/");
+            return Ok(());
+        }
+        let now = Local::now();
+        let mut entries = Vec::new();
+        for job in jobs {
+            entries.push(ListEntry {
+                next_run: scheduler::next_run_after(&job, now)?.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()),
+                schedule: scheduler::schedule_label(&job),
+                id: job.id,
+                name: job.name,
+                enabled: job.enabled,
+                last: None,
+            });
+        }
+        entries
+    };
+
+    match format {
+        ListFormat::Line => {
+            for entry in &entries {
+                let next = entry.next_run.as_deref().unwrap_or("-");
+                if !from_state {
+                    println!(
+                        "id={} enabled={} schedule={} next_run={}",
+                        entry.id, entry.enabled, entry.schedule, next
+                    );
+                    continue;
+                }
+                let last = entry
+                    .last
+                    .as_ref()
+                    .map(|r| format!("{}({})", r.status, r.ended_at.format("%m-%d %H:%M:%S")))
+                    .unwrap_or_else(|| "-".to_string());
+                let output = entry
+                    .last
+                    .as_ref()
+                    .and_then(|r| r.output_tail.last())
+                    .map(|line| format!(" output={line:?}"))
+                    .unwrap_or_default();
+                println!(
+                    "id={} enabled={} schedule={} next_run={} last={}{}",
+                    entry.id, entry.enabled, entry.schedule, next, last, output
+                );
+            }
+        }
+        ListFormat::Table | ListFormat::Csv => {
+            let header = ["id", "name", "enabled", "schedule", "next_run", "last_status"];
+            let rows: Vec<[String; 6]> = entries
+                .into_iter()
+                .map(|entry| {
+                    [
+                        entry.id,
+                        entry.name,
+                        entry.enabled.to_string(),
+                        entry.schedule,
+                        entry.next_run.unwrap_or_default(),
+                        entry.last.map(|r| r.status).unwrap_or_default(),
+                    ]
+                })
+                .collect();
+            if matches!(format, ListFormat::Csv) {
+                println!("{}", header.join(","));
+                for row in &rows {
+                    let cells: Vec<String> = row.iter().map(|cell| csv_field(cell)).collect();
+                    println!("{}", cells.join(","));
+                }
+            } else {
+                print_table(&header.map(str::to_uppercase), &rows);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn print_table<const N: usize>(header: &[String; N], rows: &[[String; N]]) {
+    let cell = |value: &str| if value.is_empty() { "-".to_string() } else { value.to_string() };
+    let mut widths = header.clone().map(|h| h.width());
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell(value).width());
+        }
+    }
+    let render = |values: Vec<String>| {
+        let padded: Vec<String> = values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{value}{}", " ".repeat(width - value.width())))
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    render(header.to_vec());
+    for row in rows {
+        render(row.iter().map(|value| cell(value)).collect());
+    }
+}
+
+struct LogFilter<'a> {
+    job_id: Option<&'a str>,
+    run_id: Option<&'a str>,
+    grep: Option<Regex>,
+    failures: bool,
+    since: Option<DateTime<Local>>,
+    until: Option<DateTime<Local>>,
+}
+
+impl LogFilter<'_> {
+    fn matches(&self, line: &str) -> bool {
+        if let Some(job) = self.job_id
+            && !line.contains(&format!("job_id={job}"))
+        {
+            return false;
+        }
+        if let Some(run_id) = self.run_id {
+            let token = format!("run_id={run_id}");
+            if !line.split_whitespace().any(|field| field == token) {
+                return false;
+            }
+        }
+        if self.failures && !is_failure_line(line) {
+            return false;
+        }
+        self.grep.as_ref().is_none_or(|re| re.is_match(line))
+    }
+}
+
+fn is_failure_line(line: &str) -> bool {
+    logging::line_level(line) == Some("ERROR")
+        || line.split_whitespace().any(|field| field == "event=failed" || field == "event=timeout")
+}
+
+fn log_files(paths: &AppPaths) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
     for entry in std::fs::read_dir(&paths.logs_dir)? {
         let entry = entry?;
@@ -143,55 +1002,279 @@ fn logs(paths: &AppPaths, job_id: Option<&str>, tail: usize) -> Result<()> {
         }
     }
     files.sort();
+    Ok(files)
+}
+
+fn logs(
+    paths: &AppPaths,
+    filter: &LogFilter<'_>,
+    tail: usize,
+    head: Option<usize>,
+    follow: bool,
+    json: bool,
+    all_files: bool,
+) -> Result<()> {
+    let (since, until) = (filter.since, filter.until);
+    // Snapshot sizes first so lines written while printing the backlog are followed, not lost.
+    let mut offsets = HashMap::new();
+    if follow {
+        for path in log_files(paths)? {
+            offsets.insert(path.clone(), path.metadata()?.len());
+        }
+    }
+    let files = log_files(paths)?;
 
-    if files.is_empty() {
+    if files.is_empty() && !follow {
         println!("no logs found");
         return Ok(());
     }
 
-    let latest = files.last().ok_or_else(|| anyhow!("no log file"))?;
-    let file = File::open(latest)?;
-    let reader = BufReader::new(file);
-    let mut lines: Vec<String> = reader.lines().collect::<std::result::Result<Vec<_>, _>>()?;
+    let mut lines = if files.is_empty() {
+        Vec::new()
+    } else if since.is_none() && until.is_none() && filter.run_id.is_none() && !filter.failures && !all_files {
+        let latest = files.last().ok_or_else(|| anyhow!("no log file"))?;
+        read_lines(latest)?
+    } else {
+        let mut lines = Vec::new();
+        for path in &files {
+            let date = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .and_then(logging::log_file_date);
+            if let Some(date) = date
+                && (since.is_some_and(|s| date < s.date_naive())
+                    || until.is_some_and(|u| date > u.date_naive()))
+            {
+                continue;
+            }
+            lines.extend(read_lines(path)?);
+        }
+        lines.retain(|line| match logging::line_timestamp(line) {
+            Some(ts) => since.is_none_or(|s| ts >= s) && until.is_none_or(|u| ts <= u),
+            None => false,
+        });
+        lines.sort_by_key(|line| logging::line_timestamp(line));
+        lines
+    };
+
+    lines.retain(|line| filter.matches(line));
+
+    let selected = match head {
+        Some(head) => &lines[..head.min(lines.len())],
+        None if tail == 0 => &lines[..],
+        None => &lines[lines.len().saturating_sub(tail)..],
+    };
+    for line in selected {
+        print_log_line(line, json)?;
+    }
 
-    if let Some(job) = job_id {
-        lines.retain(|line| line.contains(&format!("job_id={job}")));
+    if follow {
+        follow_logs(paths, filter, offsets, json)?;
     }
+    Ok(())
+}
 
-    let start = lines.len().saturating_sub(tail);
-    for line in &lines[start..] {
-        println!("{line}");
+fn follow_logs(
+    paths: &AppPaths,
+    filter: &LogFilter<'_>,
+    mut offsets: HashMap<PathBuf, u64>,
+    json: bool,
+) -> Result<()> {
+    loop {
+        std::thread::sleep(Duration::from_millis(500));
+        for path in log_files(paths)? {
+            let offset = offsets.entry(path.clone()).or_insert(0);
+            let Ok(mut file) = File::open(&path) else {
+                continue;
+            };
+            let len = file.metadata()?.len();
+            if len < *offset {
+                *offset = 0;
+            }
+            if len == *offset {
+                continue;
+            }
+            file.seek(SeekFrom::Start(*offset))?;
+            let mut buf = Vec::new();
+            file.take(len - *offset).read_to_end(&mut buf)?;
+            // Leave a partially written last line for the next poll.
+            let complete = buf.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+            *offset += complete as u64;
+            for line in String::from_utf8_lossy(&buf[..complete]).lines() {
+                let in_range = filter
+                    .since
+                    .is_none_or(|s| logging::line_timestamp(line).is_some_and(|ts| ts >= s));
+                if in_range && filter.matches(line) {
+                    print_log_line(line, json)?;
+                }
+            }
+        }
     }
+}
 
+fn print_log_line(line: &str, json: bool) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    if json {
+        let value = match logging::parse_line(line) {
+            Some(parsed) => serde_json::to_string(&parsed)?,
+            None => serde_json::json!({ "raw": line }).to_string(),
+        };
+        writeln!(out, "{value}")?;
+    } else {
+        writeln!(out, "{line}")?;
+    }
     Ok(())
 }
 
-async fn run_job(paths: &AppPaths, job_id: &str) -> Result<()> {
-    let jobs = config::load_jobs(&paths.jobs_dir)?;
+fn read_lines(path: &Path) -> Result<Vec<String>> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    Ok(reader.lines().collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+fn parse_log_time(value: &str) -> Result<DateTime<Local>> {
+    let value = value.trim();
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M"))
+        .or_else(|_| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|d| d.and_time(NaiveTime::MIN))
+        })
+        .map_err(|_| anyhow!("invalid time '{value}', expected YYYY-MM-DD[ HH:MM[:SS]]"))?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| anyhow!("time '{value}' does not exist in local timezone"))
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum RunMode {
+    Auto,
+    Inline,
+    Queue,
+}
+
+impl RunMode {
+    fn from_flags(inline: bool, queue: bool) -> Self {
+        if inline {
+            RunMode::Inline
+        } else if queue {
+            RunMode::Queue
+        } else {
+            RunMode::Auto
+        }
+    }
+}
+
+async fn run_job(paths: &AppPaths, job_id: &str, mode: RunMode, show_output: bool) -> Result<()> {
+    let jobs = config::load_jobs_skipping_collisions(paths)?.jobs;
     if !jobs.iter().any(|j| j.id == job_id) {
-        bail!("job not found: {job_id}");
+        return Err(MacrondError::JobNotFound(job_id.to_string()).into());
     }
 
-    let force_inline = std::env::var("EZCRON_FORCE_INLINE").ok().as_deref() == Some("1");
-    if daemon::daemon_running(paths)?.is_some() && !force_inline {
+    let daemon_running = daemon::daemon_running(paths)?.is_some();
+    if mode == RunMode::Queue && !daemon_running {
+        return Err(anyhow::Error::new(MacrondError::DaemonNotRunning)
+            .context(format!("cannot queue job={job_id} (use --inline)")));
+    }
+    if mode == RunMode::Queue || (mode == RunMode::Auto && daemon_running) {
         daemon::submit_run_request(paths, job_id)?;
-        println!("run request submitted for job={job_id}");
+        println!("mode=queue run request submitted for job={job_id}");
         return Ok(());
     }
 
-    let record = daemon::run_job_inline(paths, job_id).await?;
+    let record = daemon::run_job_inline(paths, job_id, show_output).await?;
     println!(
-        "job={} status={} exit_code={:?} ended_at={}",
+        "mode=inline job={} status={} exit_code={:?} signal={} ended_at={}",
         record.job_id,
         record.status,
         record.exit_code,
+        record.signal.as_deref().unwrap_or("-"),
         record.ended_at.format("%Y-%m-%d %H:%M:%S")
     );
     Ok(())
 }
 
-fn read_state(paths: &AppPaths) -> Result<DaemonState> {
-    let raw = std::fs::read_to_string(&paths.state_file)?;
-    let state = serde_json::from_str(&raw).context("parse state file")?;
-    Ok(state)
+// Prints the finished run as JSON; the exit code tells success, failure and skip apart.
+async fn run_job_and_wait(paths: &AppPaths, job_id: &str, mode: RunMode, show_output: bool) -> Result<()> {
+    let jobs = config::load_jobs_skipping_collisions(paths)?.jobs;
+    if !jobs.iter().any(|j| j.id == job_id) {
+        return Err(MacrondError::JobNotFound(job_id.to_string()).into());
+    }
+
+    let daemon_running = daemon::daemon_running(paths)?.is_some();
+    if mode == RunMode::Queue && !daemon_running {
+        return Err(anyhow::Error::new(MacrondError::DaemonNotRunning)
+            .context(format!("cannot queue job={job_id} (use --inline)")));
+    }
+    let record = if mode == RunMode::Queue || (mode == RunMode::Auto && daemon_running) {
+        let wait_id = daemon::submit_waiting_run_request(paths, job_id)?;
+        daemon::wait_run_result(paths, &wait_id).await?
+    } else {
+        daemon::run_job_inline(paths, job_id, show_output).await?
+    };
+    println!("{}", serde_json::to_string_pretty(&record)?);
+    match record.status.as_str() {
+        "success" => Ok(()),
+        "skipped" => Err(MacrondError::RunSkipped {
+            job_id: record.job_id,
+            reason: record.message,
+        }
+        .into()),
+        _ => Err(MacrondError::RunFailed {
+            job_id: record.job_id,
+            status: record.status,
+        }
+        .into()),
+    }
+}
+
+async fn run_batch(paths: &AppPaths, tag: Option<&str>, mode: RunMode, show_output: bool) -> Result<()> {
+    let jobs: Vec<_> = config::load_jobs_skipping_collisions(paths)?
+        .jobs
+        .into_iter()
+        .filter(|j| j.enabled && tag.is_none_or(|t| j.tags.iter().any(|jt| jt == t)))
+        .collect();
+    if jobs.is_empty() {
+        match tag {
+            Some(tag) => println!("no enabled jobs with tag={tag}"),
+            None => println!("no enabled jobs"),
+        }
+        return Ok(());
+    }
+
+    let daemon_running = daemon::daemon_running(paths)?.is_some();
+    if mode == RunMode::Queue && !daemon_running {
+        return Err(anyhow::Error::new(MacrondError::DaemonNotRunning).context("cannot queue jobs (use --inline)"));
+    }
+    let queue = mode == RunMode::Queue || (mode == RunMode::Auto && daemon_running);
+
+    let mut results = Vec::new();
+    for job in &jobs {
+        let result = if queue {
+            daemon::submit_run_request(paths, &job.id).map(|()| "queued".to_string())
+        } else {
+            daemon::run_job_inline(paths, &job.id, show_output)
+                .await
+                .map(|record| record.status)
+        };
+        // A job still running with allow_overlap=false was left alone, not failed.
+        let result = match result {
+            Ok(status) => status,
+            Err(err) if matches!(MacrondError::find(&err), Some(MacrondError::JobRunning { .. })) => {
+                "skipped".to_string()
+            }
+            Err(err) => format!("error({err:#})"),
+        };
+        println!("mode={} job={} result={result}", if queue { "queue" } else { "inline" }, job.id);
+        results.push(result);
+    }
+
+    let ok = results.iter().filter(|r| *r == "queued" || *r == "success").count();
+    let skipped = results.iter().filter(|r| *r == "skipped").count();
+    let failed = results.len() - ok - skipped;
+    println!("summary: jobs={} ok={ok} failed={failed} skipped={skipped}", results.len());
+    if failed > 0 {
+        bail!("{failed} of {} jobs failed", results.len());
+    }
+    Ok(())
 }
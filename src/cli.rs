@@ -1,11 +1,33 @@
+use crate::logging::LogLevel;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[command(name = "macrond", version, about = "macOS-friendly cron daemon")]
 pub struct Cli {
-    #[arg(long, default_value = ".")]
-    pub base_dir: PathBuf,
+    #[arg(long)]
+    pub base_dir: Option<PathBuf>,
+
+    #[arg(long, conflicts_with = "base_dir")]
+    pub profile: Option<String>,
+
+    #[arg(long, global = true, value_enum)]
+    pub log_level: Option<LogLevel>,
+
+    #[arg(short, long, global = true, conflicts_with = "log_level")]
+    pub verbose: bool,
+
+    #[arg(long, global = true)]
+    pub history_size: Option<usize>,
+
+    #[arg(long, global = true)]
+    pub verify_jobs: bool,
+
+    #[arg(long, global = true, value_name = "OCTAL")]
+    pub file_mode: Option<String>,
+
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
 
     #[command(subcommand)]
     pub command: Option<Command>,
@@ -14,19 +36,169 @@ pub struct Cli {
 #[derive(Debug, Subcommand)]
 pub enum Command {
     Version,
-    Start,
-    Stop,
+    Profiles,
+    Start {
+        #[arg(long, default_value_t = 60)]
+        state_interval: u64,
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(10..=3_600_000))]
+        tick_ms: u64,
+    },
+    Stop {
+        #[arg(long, default_value = "INT")]
+        signal: String,
+        #[arg(long)]
+        wait: bool,
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+    },
+    Restart {
+        #[arg(long, default_value_t = 60)]
+        state_interval: u64,
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(10..=3_600_000))]
+        tick_ms: u64,
+        #[arg(long, default_value = "INT")]
+        signal: String,
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+    },
     Status,
-    List,
-    Logs {
+    Pause {
+        #[arg(long)]
+        block_manual: bool,
+    },
+    Resume,
+    List {
+        #[arg(long, value_enum, default_value_t = ListFormat::Line)]
+        format: ListFormat,
+    },
+    Validate,
+    Seal,
+    Reload {
         #[arg(long)]
+        check: bool,
+    },
+    Logs {
+        #[arg(long, short = 'j')]
         job: Option<String>,
-        #[arg(long, default_value_t = 50)]
+        #[arg(long = "match", value_name = "TEXT", conflicts_with = "job")]
+        pattern: Option<String>,
+        #[arg(long)]
+        run_id: Option<String>,
+        #[arg(long)]
+        grep: Option<String>,
+        #[arg(long)]
+        failures: bool,
+        #[arg(long, short = 'n', default_value_t = 50)]
         tail: usize,
+        #[arg(long, conflicts_with = "tail")]
+        head: Option<usize>,
+        #[arg(long)]
+        since: Option<String>,
+        #[arg(long)]
+        until: Option<String>,
+        #[arg(long, short = 'f', conflicts_with_all = ["head", "until"])]
+        follow: bool,
+        #[arg(long)]
+        json: bool,
+        #[arg(long)]
+        all_files: bool,
     },
     Run {
-        job_id: String,
+        #[arg(required_unless_present_any = ["tag", "pattern"])]
+        job_id: Option<String>,
+        #[arg(long, conflicts_with = "job_id")]
+        tag: Option<String>,
+        #[arg(long = "match", value_name = "TEXT", conflicts_with_all = ["job_id", "tag"])]
+        pattern: Option<String>,
+        #[arg(long, conflicts_with = "queue")]
+        inline: bool,
+        #[arg(long)]
+        queue: bool,
+        #[arg(long)]
+        show_output: bool,
+        #[arg(long, conflicts_with = "tag")]
+        wait: bool,
     },
-    Tui,
-    Daemon,
+    RunAll {
+        #[arg(long, conflicts_with = "queue")]
+        inline: bool,
+        #[arg(long)]
+        queue: bool,
+    },
+    Trigger {
+        #[command(flatten)]
+        job: JobSelector,
+    },
+    Edit {
+        #[command(flatten)]
+        job: JobSelector,
+    },
+    Show {
+        #[command(flatten)]
+        job: JobSelector,
+        #[arg(long)]
+        explain: bool,
+    },
+    Next {
+        #[command(flatten)]
+        job: JobSelector,
+        #[arg(long)]
+        count: Option<usize>,
+        #[arg(long)]
+        until: Option<String>,
+    },
+    Why {
+        #[command(flatten)]
+        job: JobSelector,
+    },
+    Group {
+        #[command(subcommand)]
+        action: GroupAction,
+    },
+    Top,
+    Tui {
+        #[arg(long)]
+        plain: bool,
+        #[arg(long)]
+        read_only: bool,
+    },
+    InstallAgent {
+        #[arg(long)]
+        name: Option<String>,
+    },
+    UninstallAgent {
+        #[arg(long)]
+        name: Option<String>,
+    },
+    Daemon {
+        #[arg(long, default_value_t = 60)]
+        state_interval: u64,
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(10..=3_600_000))]
+        tick_ms: u64,
+        #[arg(long)]
+        once: bool,
+    },
+}
+
+/// A job given by exact id, or by `--match` against id and name substrings.
+#[derive(Debug, clap::Args)]
+#[group(required = true, multiple = false)]
+pub struct JobSelector {
+    pub job_id: Option<String>,
+    #[arg(long = "match", value_name = "TEXT")]
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ListFormat {
+    Line,
+    Table,
+    Csv,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum GroupAction {
+    List,
+    Enable { name: String },
+    Disable { name: String },
 }
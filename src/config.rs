@@ -1,44 +1,453 @@
-use crate::model::{JobConfig, Repeat, ScheduleConfig};
+use crate::error::MacrondError;
+use crate::model::{CommandConfig, JobConfig, Repeat, ScheduleConfig};
+use crate::paths::{self, AppPaths};
+use crate::scheduler;
 use anyhow::{Context, Result, anyhow, bail};
-use std::collections::HashSet;
-use std::path::Path;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-pub fn load_jobs(jobs_dir: &Path) -> Result<Vec<JobConfig>> {
-    let mut jobs = Vec::new();
-    let mut ids = HashSet::new();
+pub const MAX_OUTPUT_TAIL_LINES: usize = 50;
+
+pub struct LoadedJobs {
+    pub jobs: Vec<JobConfig>,
+    pub collisions: Vec<IdCollision>,
+    pub rejected: Vec<ChecksumMismatch>,
+}
+
+/// A job file skipped because it does not match its `.sha256` sidecar.
+#[derive(Debug)]
+pub struct ChecksumMismatch {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "checksum check failed for {}: {}", self.path.display(), self.reason)
+    }
+}
+
+static VERIFY_CHECKSUMS: AtomicBool = AtomicBool::new(false);
+
+/// Integrity mode: job files must match their `.sha256` sidecar to be loaded.
+pub fn set_verify_checksums(on: bool) {
+    VERIFY_CHECKSUMS.store(on, Ordering::Relaxed);
+}
+
+pub fn verify_checksums() -> bool {
+    VERIFY_CHECKSUMS.load(Ordering::Relaxed)
+}
+
+#[derive(Debug)]
+pub struct IdCollision {
+    pub id: String,
+    pub kept: PathBuf,
+    pub skipped: PathBuf,
+}
+
+impl std::fmt::Display for IdCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "duplicate job id: {} ({} and {})", self.id, self.kept.display(), self.skipped.display())
+    }
+}
+
+fn describe_collisions(jobs_dir: &Path, collisions: &[IdCollision]) -> String {
+    let name = |path: &Path| path.strip_prefix(jobs_dir).unwrap_or(path).display().to_string();
+    let files: Vec<String> = collisions
+        .iter()
+        .map(|c| format!("{} (id {} kept in {})", name(&c.skipped), c.id, name(&c.kept)))
+        .collect();
+    format!("duplicate job ids, skipped {}", files.join(", "))
+}
+
+fn describe_rejected(jobs_dir: &Path, rejected: &[ChecksumMismatch]) -> String {
+    let files: Vec<String> = rejected
+        .iter()
+        .map(|r| r.path.strip_prefix(jobs_dir).unwrap_or(&r.path).display().to_string())
+        .collect();
+    format!("checksum mismatch, skipped {}", files.join(", "))
+}
+
+/// One-line summary of the files a lenient load left out, if any.
+pub fn describe_skipped(jobs_dir: &Path, loaded: &LoadedJobs) -> Option<String> {
+    let mut parts = Vec::new();
+    if !loaded.collisions.is_empty() {
+        parts.push(describe_collisions(jobs_dir, &loaded.collisions));
+    }
+    if !loaded.rejected.is_empty() {
+        parts.push(describe_rejected(jobs_dir, &loaded.rejected));
+    }
+    (!parts.is_empty()).then(|| parts.join("; "))
+}
+
+pub fn load_jobs(paths: &AppPaths) -> Result<Vec<JobConfig>, MacrondError> {
+    let mut loaded = load_jobs_skipping_collisions(paths)?;
+    if !loaded.rejected.is_empty() {
+        let rejected = loaded.rejected.remove(0);
+        return Err(MacrondError::InvalidConfig { path: rejected.path, message: rejected.reason });
+    }
+    if !loaded.collisions.is_empty() {
+        return Err(MacrondError::DuplicateId(loaded.collisions.remove(0)));
+    }
+    Ok(loaded.jobs)
+}
+
+// Files that reuse an id already taken are skipped instead of failing the whole load.
+// The file named after the id wins (the shallowest one if several are), otherwise the first
+// one by path.
+pub fn load_jobs_skipping_collisions(paths: &AppPaths) -> Result<LoadedJobs, MacrondError> {
+    let mut loaded = LoadedJobs { jobs: Vec::new(), collisions: Vec::new(), rejected: Vec::new() };
+    if let Some(file) = &paths.config_file {
+        loaded.jobs = load_config_file(file)?;
+        return Ok(loaded);
+    }
+    let jobs_dir = &paths.jobs_dir;
+    let defaults = load_defaults(&paths.defaults_file)?;
 
     if !jobs_dir.exists() {
-        return Ok(jobs);
+        return Ok(loaded);
     }
 
-    for entry in std::fs::read_dir(jobs_dir).context("read jobs dir")? {
-        let entry = entry?;
-        let path = entry.path();
-        if !path.is_file() {
+    let mut paths = Vec::new();
+    collect_job_files(jobs_dir, &mut paths)?;
+    paths.sort();
+
+    let mut by_id: HashMap<String, Vec<(PathBuf, JobConfig)>> = HashMap::new();
+    for path in paths {
+        if verify_checksums()
+            && let Err(reason) = check_checksum(&path)
+        {
+            loaded.rejected.push(ChecksumMismatch { path, reason });
             continue;
         }
-        if path.extension().and_then(|s| s.to_str()) != Some("json") {
-            continue;
+        let job = load_job_file(&path, defaults.as_ref())?;
+        by_id.entry(job.id.clone()).or_default().push((path, job));
+    }
+
+    for (id, mut files) in by_id {
+        let keep = files
+            .iter()
+            .enumerate()
+            .filter(|(_, (path, _))| path.file_stem().and_then(|s| s.to_str()) == Some(id.as_str()))
+            .min_by_key(|(_, (path, _))| path.components().count())
+            .map_or(0, |(idx, _)| idx);
+        let (kept, job) = files.remove(keep);
+        for (skipped, _) in files {
+            loaded.collisions.push(IdCollision { id: id.clone(), kept: kept.clone(), skipped });
         }
+        loaded.jobs.push(job);
+    }
 
-        let raw = std::fs::read_to_string(&path)
-            .with_context(|| format!("read job file {}", path.display()))?;
-        let job: JobConfig = serde_json::from_str(&raw)
-            .with_context(|| format!("parse job file {}", path.display()))?;
-        validate_job(&job).with_context(|| format!("invalid job {}", job.id))?;
+    loaded.jobs.sort_by(|a, b| a.id.cmp(&b.id));
+    loaded.collisions.sort_by(|a, b| a.skipped.cmp(&b.skipped));
+    Ok(loaded)
+}
 
-        if !ids.insert(job.id.clone()) {
-            bail!("duplicate job id: {}", job.id);
-        }
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CombinedConfig {
+    #[serde(default)]
+    defaults: Option<Value>,
+    #[serde(default)]
+    jobs: Vec<Value>,
+}
 
-        jobs.push(job);
+// The file is one unit: a bad entry or a duplicate id rejects all of it, so the daemon keeps
+// the jobs it already had instead of running half a config.
+pub fn load_config_file(path: &Path) -> Result<Vec<JobConfig>, MacrondError> {
+    if verify_checksums()
+        && let Err(reason) = check_checksum(path)
+    {
+        return Err(MacrondError::InvalidConfig { path: path.to_path_buf(), message: reason });
     }
+    parse_config_file(path)
+}
 
+fn parse_config_file(path: &Path) -> Result<Vec<JobConfig>, MacrondError> {
+    let invalid = |message: String| MacrondError::InvalidConfig { path: path.to_path_buf(), message };
+    let raw = std::fs::read_to_string(path).map_err(|source| MacrondError::Io { path: path.to_path_buf(), source })?;
+    let value: Value = match path.extension().and_then(|s| s.to_str()) {
+        Some("yaml" | "yml") => serde_yaml::from_str(&raw).map_err(|err| invalid(err.to_string()))?,
+        _ => serde_json::from_str(&raw).map_err(|err| invalid(err.to_string()))?,
+    };
+    let config: CombinedConfig = serde_json::from_value(value).map_err(|err| invalid(err.to_string()))?;
+    if config.defaults.as_ref().is_some_and(|d| !d.is_object()) {
+        return Err(invalid("defaults must be an object".to_string()));
+    }
+    let mut jobs: Vec<JobConfig> = Vec::new();
+    for (idx, mut value) in config.jobs.into_iter().enumerate() {
+        if let Some(defaults) = &config.defaults {
+            merge_defaults(&mut value, defaults);
+        }
+        let job: JobConfig = serde_json::from_value(value).map_err(|err| invalid(format!("jobs[{idx}]: {err}")))?;
+        validate_job(&job).map_err(|err| invalid(format!("jobs[{idx}] job {}: {err:#}", job.id)))?;
+        if jobs.iter().any(|j| j.id == job.id) {
+            return Err(invalid(format!("jobs[{idx}]: duplicate job id {}", job.id)));
+        }
+        jobs.push(job);
+    }
     jobs.sort_by(|a, b| a.id.cmp(&b.id));
     Ok(jobs)
 }
 
+fn collect_job_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), MacrondError> {
+    let io = |source| MacrondError::Io { path: dir.to_path_buf(), source };
+    for entry in std::fs::read_dir(dir).map_err(io)? {
+        let entry = entry.map_err(io)?;
+        let path = entry.path();
+        // Symlinked directories are not followed, so a link back up the tree cannot loop.
+        if entry.file_type().map_err(io)?.is_dir() {
+            if !entry.file_name().to_string_lossy().starts_with('.') {
+                collect_job_files(&path, out)?;
+            }
+            continue;
+        }
+        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Jobs whose id or name contains `pattern` (case-insensitive); an exact id wins outright so
+/// `backup` still picks `backup` when `backup_db` exists.
+pub fn match_jobs<'a>(jobs: &'a [JobConfig], pattern: &str) -> Vec<&'a JobConfig> {
+    if let Some(job) = jobs.iter().find(|j| j.id == pattern) {
+        return vec![job];
+    }
+    let needle = pattern.to_lowercase();
+    jobs.iter()
+        .filter(|j| j.id.to_lowercase().contains(&needle) || j.name.to_lowercase().contains(&needle))
+        .collect()
+}
+
+/// Where the file for `job_id` lives: `<job_id>.json` anywhere under `jobs_dir` (the shallowest
+/// match), or the top level for a job that does not exist yet.
+pub fn job_file_path(jobs_dir: &Path, job_id: &str) -> PathBuf {
+    let name = format!("{job_id}.json");
+    let top = jobs_dir.join(&name);
+    if top.exists() {
+        return top;
+    }
+    let mut files = Vec::new();
+    let _ = collect_job_files(jobs_dir, &mut files);
+    files.sort();
+    files
+        .into_iter()
+        .filter(|path| path.file_name().is_some_and(|n| *n == *name))
+        .min_by_key(|path| path.components().count())
+        .unwrap_or(top)
+}
+
+pub fn checksum_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".sha256");
+    path.with_file_name(name)
+}
+
+fn file_checksum(path: &Path) -> std::io::Result<String> {
+    Ok(format!("{:x}", Sha256::digest(std::fs::read(path)?)))
+}
+
+fn check_checksum(path: &Path) -> Result<(), String> {
+    let sidecar = checksum_path(path);
+    let raw = std::fs::read_to_string(&sidecar).map_err(|_| format!("missing {}", sidecar.display()))?;
+    let expected = raw.split_whitespace().next().unwrap_or_default().to_ascii_lowercase();
+    let actual = file_checksum(path).map_err(|err| err.to_string())?;
+    if expected != actual {
+        return Err(format!("sha256 is {actual}, {} expects {expected}", sidecar.display()));
+    }
+    Ok(())
+}
+
+/// Writes the `.sha256` sidecar (in `sha256sum` format) for a job file.
+fn write_checksum(path: &Path) -> Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let sidecar = checksum_path(path);
+    paths::write_file(&sidecar, format!("{}  {name}\n", file_checksum(path)?))
+        .with_context(|| format!("write {}", sidecar.display()))
+}
+
+// Keeps sidecars current: always in integrity mode, otherwise only for files already sealed.
+pub fn update_checksum(path: &Path) -> Result<()> {
+    if verify_checksums() || checksum_path(path).exists() {
+        write_checksum(path)?;
+    }
+    Ok(())
+}
+
+pub fn seal_config_file(path: &Path) -> Result<Vec<PathBuf>> {
+    parse_config_file(path)?;
+    write_checksum(path)?;
+    Ok(vec![path.to_path_buf()])
+}
+
+/// Trusts the current content of every job file; refuses if any of them is invalid.
+pub fn seal_job_files(jobs_dir: &Path, defaults_file: &Path) -> Result<Vec<PathBuf>> {
+    let defaults = load_defaults(defaults_file)?;
+    let mut paths = Vec::new();
+    if jobs_dir.exists() {
+        collect_job_files(jobs_dir, &mut paths)?;
+    }
+    paths.sort();
+    for path in &paths {
+        load_job_file(path, defaults.as_ref())?;
+    }
+    for path in &paths {
+        write_checksum(path)?;
+    }
+    Ok(paths)
+}
+
+pub fn remove_job_file(path: &Path) -> Result<()> {
+    std::fs::remove_file(path).with_context(|| format!("remove {}", path.display()))?;
+    let _ = std::fs::remove_file(checksum_path(path));
+    Ok(())
+}
+
+pub fn load_job_file(path: &Path, defaults: Option<&Value>) -> Result<JobConfig, MacrondError> {
+    let invalid = |message: String| MacrondError::InvalidConfig { path: path.to_path_buf(), message };
+    let raw = std::fs::read_to_string(path).map_err(|source| MacrondError::Io { path: path.to_path_buf(), source })?;
+    let mut value: Value = serde_json::from_str(&raw).map_err(|err| invalid(err.to_string()))?;
+    if let Some(defaults) = defaults {
+        merge_defaults(&mut value, defaults);
+    }
+    let job: JobConfig = serde_json::from_value(value).map_err(|err| invalid(err.to_string()))?;
+    validate_job(&job).map_err(|err| invalid(format!("job {}: {err:#}", job.id)))?;
+    Ok(job)
+}
+
+pub fn set_job_enabled(jobs_dir: &Path, job_id: &str, enabled: bool) -> Result<()> {
+    update_job_file(jobs_dir, job_id, |obj| {
+        obj.insert("enabled".to_string(), Value::Bool(enabled));
+        Ok(())
+    })
+}
+
+pub fn mark_job_completed(jobs_dir: &Path, job_id: &str, at: DateTime<Local>) -> Result<()> {
+    update_job_file(jobs_dir, job_id, |obj| {
+        obj.insert("enabled".to_string(), Value::Bool(false));
+        obj.insert("completed_at".to_string(), serde_json::to_value(at)?);
+        Ok(())
+    })
+}
+
+// Edits the raw JSON so fields macrond does not model and the user's key order survive.
+fn update_job_file(
+    jobs_dir: &Path,
+    job_id: &str,
+    update: impl FnOnce(&mut serde_json::Map<String, Value>) -> Result<()>,
+) -> Result<()> {
+    let path = job_file_path(jobs_dir, job_id);
+    if verify_checksums()
+        && let Err(reason) = check_checksum(&path)
+    {
+        bail!("refusing to update {}: checksum check failed: {reason}", path.display());
+    }
+    let raw = std::fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))?;
+    let mut value: Value = serde_json::from_str(&raw)?;
+    let obj = value
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("job file is not a JSON object: {}", path.display()))?;
+    update(obj)?;
+    obj.insert("updated_at".to_string(), serde_json::to_value(Local::now())?);
+    paths::write_file(&path, serde_json::to_vec_pretty(&value)?)?;
+    update_checksum(&path)
+}
+
+pub struct GroupUpdate {
+    pub changed: Vec<String>,
+    pub failed: Vec<(String, anyhow::Error)>,
+}
+
+// Writes every member that needs the change; one failed file does not stop the others.
+// The daemon's reload debounce folds the burst of writes into a single reload.
+pub fn set_group_enabled(
+    jobs_dir: &Path,
+    jobs: &[JobConfig],
+    group: &str,
+    enabled: bool,
+) -> Result<GroupUpdate> {
+    let members: Vec<&JobConfig> = jobs.iter().filter(|j| j.group.as_deref() == Some(group)).collect();
+    if members.is_empty() {
+        bail!("no jobs in group '{group}'");
+    }
+    let mut changed = Vec::new();
+    let mut failed = Vec::new();
+    for job in members.into_iter().filter(|j| j.enabled != enabled) {
+        match set_job_enabled(jobs_dir, &job.id, enabled) {
+            Ok(()) => changed.push(job.id.clone()),
+            Err(err) => failed.push((job.id.clone(), err)),
+        }
+    }
+    Ok(GroupUpdate { changed, failed })
+}
+
+pub fn write_job_file(path: &Path, job: &JobConfig) -> Result<()> {
+    let mut value = serde_json::to_value(job)?;
+    if let Ok(raw) = std::fs::read_to_string(path)
+        && let Ok(existing) = serde_json::from_str::<Value>(&raw)
+    {
+        value = keep_key_order(&existing, value);
+    }
+    paths::write_file(path, serde_json::to_vec_pretty(&value)?).with_context(|| format!("write {}", path.display()))?;
+    update_checksum(path)
+}
+
+// Rebuilds `fresh` in the key order of `existing` so saving a hand-written file keeps
+// diffs small; keys only in `fresh` go last, and unset (null) ones are left out.
+fn keep_key_order(existing: &Value, fresh: Value) -> Value {
+    match (existing, fresh) {
+        (Value::Object(existing), Value::Object(mut fresh)) => {
+            let mut ordered = serde_json::Map::new();
+            for (key, old) in existing {
+                if let Some(new) = fresh.shift_remove(key) {
+                    ordered.insert(key.clone(), keep_key_order(old, new));
+                }
+            }
+            ordered.extend(fresh.into_iter().filter(|(_, v)| !v.is_null()));
+            Value::Object(ordered)
+        }
+        (_, fresh) => fresh,
+    }
+}
+
+pub fn load_defaults(defaults_file: &Path) -> Result<Option<Value>, MacrondError> {
+    if !defaults_file.exists() {
+        return Ok(None);
+    }
+    let invalid = |message: String| MacrondError::InvalidConfig { path: defaults_file.to_path_buf(), message };
+    let raw = std::fs::read_to_string(defaults_file)
+        .map_err(|source| MacrondError::Io { path: defaults_file.to_path_buf(), source })?;
+    let value: Value = serde_json::from_str(&raw).map_err(|err| invalid(err.to_string()))?;
+    if !value.is_object() {
+        return Err(invalid("defaults file must contain a JSON object".to_string()));
+    }
+    Ok(Some(value))
+}
+
+fn merge_defaults(value: &mut Value, defaults: &Value) {
+    let (Value::Object(target), Value::Object(defaults)) = (value, defaults) else {
+        return;
+    };
+    for (key, default) in defaults {
+        match target.get_mut(key) {
+            Some(existing) if !existing.is_null() => merge_defaults(existing, default),
+            _ => {
+                target.insert(key.clone(), default.clone());
+            }
+        }
+    }
+}
+
 fn validate_job(job: &JobConfig) -> Result<()> {
     if job.id.trim().is_empty() {
         bail!("job.id is required");
@@ -46,8 +455,60 @@ fn validate_job(job: &JobConfig) -> Result<()> {
     if job.name.trim().is_empty() {
         bail!("job.name is required");
     }
-    if job.command.program.trim().is_empty() {
-        bail!("command.program is required");
+    validate_command("command", &job.command)?;
+    if job.group.as_deref().is_some_and(|g| g.trim().is_empty()) {
+        bail!("group must not be empty");
+    }
+    if job.max_manual_runs == Some(0) {
+        bail!("max_manual_runs must be at least 1");
+    }
+    if job.disable_after_failures == Some(0) {
+        bail!("disable_after_failures must be at least 1");
+    }
+    if let Some(lines) = job.output_tail_lines
+        && !(1..=MAX_OUTPUT_TAIL_LINES).contains(&lines)
+    {
+        bail!("output_tail_lines must be 1..={MAX_OUTPUT_TAIL_LINES}");
+    }
+    if let Some(hook) = &job.on_success {
+        validate_command("on_success", hook)?;
+    }
+    if let Some(hook) = &job.on_failure {
+        validate_command("on_failure", hook)?;
+    }
+    if let Some(hook) = &job.on_slow {
+        validate_command("on_slow", hook)?;
+    }
+    for (name, url) in [("ping_url_on_success", &job.ping_url_on_success), ("ping_url_on_start", &job.ping_url_on_start)] {
+        if let Some(url) = url
+            && !(url.starts_with("http://") || url.starts_with("https://"))
+        {
+            bail!("{name} must be an http:// or https:// URL");
+        }
+    }
+    if let Some(factor) = job.slow_anomaly_factor
+        && !(factor.is_finite() && factor > 1.0)
+    {
+        bail!("slow_anomaly_factor must be greater than 1");
+    }
+    if let Some(warn) = job.warn_after_seconds {
+        if warn == 0 {
+            bail!("warn_after_seconds must be at least 1");
+        }
+        if job.timeout_seconds > 0 && warn >= job.timeout_seconds {
+            bail!("warn_after_seconds must be less than timeout_seconds");
+        }
+    }
+
+    if let (Some(from), Some(until)) = scheduler::active_window(job)?
+        && from > until
+    {
+        bail!("active_from must not be later than active_until");
+    }
+    if job.delete_after_run
+        && !matches!(job.schedule, ScheduleConfig::Simple { repeat: Repeat::Once, .. })
+    {
+        bail!("delete_after_run only applies to once schedules");
     }
 
     match &job.schedule {
@@ -55,16 +516,40 @@ fn validate_job(job: &JobConfig) -> Result<()> {
             let _ = cron::Schedule::from_str(expression)
                 .map_err(|e| anyhow!("invalid cron expression: {e}"))?;
         }
+        ScheduleConfig::Interval { every_seconds, .. } => {
+            if *every_seconds == 0 {
+                bail!("every_seconds must be at least 1");
+            }
+        }
         ScheduleConfig::Simple {
             repeat,
             time,
+            times,
             weekday,
             day,
+            week,
             once_at,
+            minute,
+            month,
+            dst_gap,
         } => {
+            if week.is_some() && !matches!(repeat, Repeat::MonthlyWeekday) {
+                bail!("week is only allowed for monthlyweekday");
+            }
+            if !times.is_empty() && !matches!(repeat, Repeat::Daily) {
+                bail!("times is only allowed for daily");
+            }
             match repeat {
                 Repeat::Daily => {
-                    validate_hhmm(time.as_deref())?;
+                    if time.is_none() && times.is_empty() {
+                        bail!("time or times is required for daily");
+                    }
+                    if time.is_some() {
+                        validate_hhmm(time.as_deref())?;
+                    }
+                    for t in times {
+                        validate_hhmm(Some(t))?;
+                    }
                 }
                 Repeat::Weekly => {
                     let w = weekday.ok_or_else(|| anyhow!("weekday is required for weekly"))?;
@@ -75,8 +560,19 @@ fn validate_job(job: &JobConfig) -> Result<()> {
                 }
                 Repeat::Monthly => {
                     let d = day.ok_or_else(|| anyhow!("day is required for monthly"))?;
-                    if !(1..=31).contains(&d) {
-                        bail!("day must be 1..=31");
+                    if d > 31 {
+                        bail!("day must be 1..=31, or 0/\"last\" for the last day of the month");
+                    }
+                    validate_hhmm(time.as_deref())?;
+                }
+                Repeat::MonthlyWeekday => {
+                    let w = week.ok_or_else(|| anyhow!("week is required for monthlyweekday"))?;
+                    if !((1..=4).contains(&w) || w == scheduler::LAST_WEEK) {
+                        bail!("week must be 1..=4, or -1/\"last\" for the last one in the month");
+                    }
+                    let d = weekday.ok_or_else(|| anyhow!("weekday is required for monthlyweekday"))?;
+                    if !(1..=7).contains(&d) {
+                        bail!("weekday must be 1..=7");
                     }
                     validate_hhmm(time.as_deref())?;
                 }
@@ -85,12 +581,32 @@ fn validate_job(job: &JobConfig) -> Result<()> {
                         bail!("time is not allowed for everyminute");
                     }
                 }
+                Repeat::Hourly => {
+                    if time.is_some() {
+                        bail!("time is not allowed for hourly, use minute");
+                    }
+                    let m = minute.ok_or_else(|| anyhow!("minute is required for hourly"))?;
+                    if m > 59 {
+                        bail!("minute must be 0..=59");
+                    }
+                }
+                Repeat::Yearly => {
+                    let m = month.ok_or_else(|| anyhow!("month is required for yearly"))?;
+                    if !(1..=12).contains(&m) {
+                        bail!("month must be 1..=12");
+                    }
+                    let d = day.ok_or_else(|| anyhow!("day is required for yearly"))?;
+                    let max_day = scheduler::max_day_of_month(m);
+                    if !(1..=max_day).contains(&d) {
+                        bail!("day must be 1..={max_day} for month {m}");
+                    }
+                    validate_hhmm(time.as_deref())?;
+                }
                 Repeat::Once => {
                     let once = once_at
                         .as_deref()
                         .ok_or_else(|| anyhow!("once_at is required for once"))?;
-                    chrono::NaiveDateTime::parse_from_str(once, "%Y-%m-%d %H:%M")
-                        .map_err(|e| anyhow!("invalid once_at format: {e}"))?;
+                    scheduler::parse_once_at(once, *dst_gap)?;
                 }
             }
         }
@@ -99,6 +615,137 @@ fn validate_job(job: &JobConfig) -> Result<()> {
     Ok(())
 }
 
+pub fn schedule_warnings(job: &JobConfig, now: DateTime<Local>) -> Vec<String> {
+    if !job.enabled || job.manual_only {
+        return Vec::new();
+    }
+    match scheduler::next_run_after(job, now) {
+        Ok(Some(_)) => Vec::new(),
+        Ok(None) if job.active_until.is_some() => {
+            let until = job.active_until.as_deref().unwrap_or("-");
+            if scheduler::in_active_window(job, now) {
+                vec![format!("no scheduled run falls before active_until '{until}', job will never run")]
+            } else {
+                vec![format!("active_until '{until}' is in the past, job will never run")]
+            }
+        }
+        Ok(None) => match &job.schedule {
+            ScheduleConfig::Cron { expression } => {
+                vec![format!("cron expression '{expression}' never matches, job will never run")]
+            }
+            ScheduleConfig::Interval { .. } => Vec::new(),
+            ScheduleConfig::Simple { once_at, dst_gap, .. } => {
+                let once_at = once_at.as_deref().unwrap_or("-");
+                match scheduler::parse_once_at(once_at, *dst_gap) {
+                    Ok(None) => vec![format!(
+                        "once_at '{once_at}' falls in a DST gap and dst_gap is skip, job will never run"
+                    )],
+                    _ => vec![format!("once_at '{once_at}' is in the past, job will never run")],
+                }
+            }
+        },
+        Err(err) => vec![format!("cannot compute next run: {err:#}")],
+    }
+}
+
+pub fn resolve_working_dir(base_dir: &Path, working_dir: &str) -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    match (working_dir.strip_prefix('~'), home) {
+        (Some(""), Some(home)) => home,
+        (Some(rest), Some(home)) if rest.starts_with('/') => home.join(rest.trim_start_matches('/')),
+        _ => base_dir.join(working_dir),
+    }
+}
+
+const DATE_PLACEHOLDER: &str = "{date:";
+
+/// Replaces `{date:FORMAT}` tokens (strftime syntax) with `at`; `\{date:` stays literal.
+pub fn expand_placeholders(value: &str, at: DateTime<Local>) -> Result<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(idx) = rest.find(DATE_PLACEHOLDER) {
+        let after = &rest[idx + DATE_PLACEHOLDER.len()..];
+        if let Some(literal) = rest[..idx].strip_suffix('\\') {
+            out.push_str(literal);
+            out.push_str(DATE_PLACEHOLDER);
+            rest = after;
+            continue;
+        }
+        out.push_str(&rest[..idx]);
+        let end = after
+            .find('}')
+            .ok_or_else(|| anyhow!("unterminated {DATE_PLACEHOLDER}...}} placeholder in '{value}'"))?;
+        let format = &after[..end];
+        if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+            bail!("invalid date format '{format}' in '{value}'");
+        }
+        write!(out, "{}", at.format(format))?;
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+pub fn expand_command(command: &CommandConfig, at: DateTime<Local>) -> Result<CommandConfig> {
+    let mut expanded = command.clone();
+    for arg in &mut expanded.args {
+        *arg = expand_placeholders(arg, at)?;
+    }
+    if let Some(working_dir) = &mut expanded.working_dir {
+        *working_dir = expand_placeholders(working_dir, at)?;
+    }
+    Ok(expanded)
+}
+
+pub fn check_working_dir(base_dir: &Path, command: &CommandConfig) -> Result<()> {
+    if let Some(working_dir) = &command.working_dir {
+        let resolved = resolve_working_dir(base_dir, working_dir);
+        if !resolved.is_dir() {
+            bail!("working_dir '{working_dir}' does not exist ({})", resolved.display());
+        }
+    }
+    Ok(())
+}
+
+pub fn check_job_working_dirs(base_dir: &Path, job: &JobConfig) -> Result<()> {
+    let now = Local::now();
+    for command in std::iter::once(&job.command).chain(&job.on_success).chain(&job.on_failure).chain(&job.on_slow) {
+        check_working_dir(base_dir, &expand_command(command, now)?)?;
+    }
+    Ok(())
+}
+
+fn validate_command(name: &str, command: &CommandConfig) -> Result<()> {
+    if command.program.trim().is_empty() {
+        bail!("{name}.program is required");
+    }
+    expand_command(command, Local::now()).with_context(|| format!("{name} placeholders"))?;
+    if let Some(nice) = command.nice
+        && !(-20..=19).contains(&nice)
+    {
+        bail!("{name}.nice must be -20..=19");
+    }
+    if command.env_file.as_deref().is_some_and(|v| v.trim().is_empty()) {
+        bail!("{name}.env_file must not be empty");
+    }
+    if command.env_remove.iter().any(|key| key.is_empty() || key.contains('=')) {
+        bail!("{name}.env_remove entries must be non-empty variable names without '='");
+    }
+    if command.args_file.as_deref().is_some_and(|v| v.trim().is_empty()) {
+        bail!("{name}.args_file must not be empty");
+    }
+    if command.arg0.as_deref().is_some_and(|v| v.is_empty()) {
+        bail!("{name}.arg0 must not be empty");
+    }
+    if command.run_as_user.as_deref().is_some_and(|v| v.trim().is_empty()) {
+        bail!("{name}.run_as_user must not be empty");
+    }
+    if command.run_as_group.as_deref().is_some_and(|v| v.trim().is_empty()) {
+        bail!("{name}.run_as_group must not be empty");
+    }
+    Ok(())
+}
+
 fn validate_hhmm(time: Option<&str>) -> Result<()> {
     let time = time.ok_or_else(|| anyhow!("time is required"))?;
     let parts: Vec<&str> = time.split(':').collect();
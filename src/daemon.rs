@@ -1,177 +1,990 @@
-use crate::config;
+use crate::config::{self, LoadedJobs};
+use crate::error::MacrondError;
 use crate::logging;
-use crate::model::{DaemonState, ExecutionRecord, JobConfig, JobView};
-use crate::paths::AppPaths;
+use crate::model::{
+    CommandConfig, DaemonState, DurationStats, ExecutionRecord, JobConfig, JobView, Repeat, ScheduleConfig,
+};
+use crate::paths::{self, AppPaths};
 use crate::scheduler;
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use chrono::Local;
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use std::collections::HashMap;
+use nix::errno::Errno;
+use nix::fcntl::{Flock, FlockArg};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::Path;
+use std::os::unix::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
 use std::process::Stdio;
-use tokio::process::Command;
+use tokio::io::AsyncBufReadExt;
+use tokio::process::{ChildStdout, Command};
+use tokio::signal::unix::{SignalKind, signal};
 use tokio::sync::mpsc;
-use tokio::time::{Duration, interval};
+use tokio::time::{Duration, Instant, interval};
 use uuid::Uuid;
 
-pub async fn run_daemon(paths: AppPaths) -> Result<()> {
+const HOOK_TIMEOUT_SECONDS: u64 = 30;
+const PING_TIMEOUT_SECONDS: u64 = 10;
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+pub const DEFAULT_TICK_MS: u64 = 1000;
+const STALL_TICKS: u32 = 3;
+// Wall-clock drift against the monotonic clock between two ticks that counts as a jump.
+const CLOCK_JUMP_SECONDS: i64 = 60;
+const MAX_OUTPUT_LINE_CHARS: usize = 200;
+pub const DEFAULT_HISTORY_SIZE: usize = 100;
+pub const MAX_HISTORY_SIZE: usize = 10_000;
+const DURATION_EMA_ALPHA: f64 = 0.3;
+const DURATION_MIN_SAMPLES: u64 = 3;
+const REQUEST_PARSE_ATTEMPTS: u32 = 3;
+
+static HISTORY_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_HISTORY_SIZE);
+
+/// Number of runs kept in `recent_runs` and log lines shown in the TUI history.
+pub fn set_history_size(size: usize) {
+    HISTORY_SIZE.store(size.clamp(1, MAX_HISTORY_SIZE), Ordering::Relaxed);
+}
+
+pub fn history_size() -> usize {
+    HISTORY_SIZE.load(Ordering::Relaxed)
+}
+
+pub async fn run_daemon(paths: AppPaths, state_interval: u64, tick_ms: u64, once: bool) -> Result<()> {
     paths.ensure_dirs()?;
-    if let Some(pid) = read_pid(&paths.pid_file)? {
-        if is_pid_running(pid) {
-            return Err(anyhow!("daemon is already running with pid {pid}"));
-        }
-    }
+    paths.check_writable()?;
+    let Some(_daemon_lock) = lock_daemon(&paths)? else {
+        let pid = read_pid(&paths.pid_file)?.unwrap_or(0);
+        return Err(MacrondError::DaemonAlreadyRunning(pid).into());
+    };
 
     write_pid(&paths.pid_file)?;
     let _pid_guard = PidGuard {
         path: paths.pid_file.clone(),
     };
 
-    logging::log_daemon(&paths.logs_dir, "INFO", "daemon started")?;
+    let mut message = if once { "daemon started once=true".to_string() } else { "daemon started".to_string() };
+    if let Some(file) = &paths.config_file {
+        message.push_str(&format!(" config={}", file.display()));
+    }
+    logging::log_daemon(&paths.logs_dir, "INFO", &message)?;
     logging::cleanup_old_logs(&paths.logs_dir, 30)?;
+    // Results nobody collected belong to waiters that gave up when the previous daemon exited.
+    for entry in std::fs::read_dir(&paths.results_dir)?.flatten() {
+        let _ = std::fs::remove_file(entry.path());
+    }
 
-    let mut last_reload_error: Option<String> = None;
-    let mut jobs = match config::load_jobs(&paths.jobs_dir) {
-        Ok(v) => v,
+    let mut rt = Runtime {
+        tick_ms: Some(tick_ms),
+        catchup_before: Some(Local::now()),
+        ..Runtime::default()
+    };
+    let mut last_check = None;
+    let mut persisted_next_runs = HashMap::new();
+    if let Ok(state) = read_state(&paths) {
+        last_check = Some(state.updated_at);
+        persisted_next_runs = state.jobs.iter().filter_map(|j| Some((j.id.clone(), j.next_run?))).collect();
+        rt.restore_history(state);
+    }
+    // Kept from the previous state so restarts do not reset the cadence of interval schedules.
+    scheduler::set_start_anchor(*rt.daemon_started_at.get_or_insert_with(Local::now));
+    rt.jobs = match config::load_jobs_skipping_collisions(&paths) {
+        Ok(loaded) => {
+            cleanup_run_markers(&paths, &loaded.jobs)?;
+            rt.last_reload_error = log_skipped_files(&paths, &loaded)?;
+            loaded.jobs
+        }
         Err(err) => {
             let msg = format!("initial load failed: {err:#}");
             logging::log_daemon(&paths.logs_dir, "ERROR", &msg)?;
-            last_reload_error = Some(msg);
+            rt.last_reload_error = Some(msg);
             Vec::new()
         }
     };
-
-    let mut next_runs = compute_next_runs(&jobs);
-    let mut last_result: HashMap<String, ExecutionRecord> = HashMap::new();
-    let mut recent_runs: Vec<ExecutionRecord> = Vec::new();
+    // A one-shot pass has no previous tick to compare against, so it fires every
+    // slot that came due since the last state write.
+    let since = if once { last_check.unwrap_or_else(Local::now) } else { Local::now() };
+    rt.next_runs = compute_next_runs(&rt.jobs, since);
+    restore_next_runs(&paths, &mut rt, &persisted_next_runs, Local::now())?;
+    let unscheduled: Vec<JobConfig> = rt
+        .jobs
+        .iter()
+        .filter(|j| rt.next_runs.get(&j.id).copied().flatten().is_none())
+        .cloned()
+        .collect();
+    log_schedule_warnings(&paths, &unscheduled)?;
+    log_tick_warnings(&paths, &rt.jobs, rt.tick())?;
+    log_next_runs(&paths, &rt)?;
+    rt.dirty = true;
 
     let (tx_run, mut rx_run) = mpsc::channel::<ExecutionRecord>(256);
 
+    if once {
+        run_tick(&paths, &mut rt, &tx_run, &mut rx_run)?;
+        drop(tx_run);
+        while let Some(record) = rx_run.recv().await {
+            handle_run_record(&paths, &mut rt, record)?;
+        }
+        rt.next_runs = compute_next_runs(&rt.jobs, Local::now());
+        write_state(&paths, std::process::id(), false, &rt)?;
+        logging::log_daemon(&paths.logs_dir, "INFO", "daemon stopped")?;
+        return Ok(());
+    }
+
     let (event_tx, event_rx) = std::sync::mpsc::channel();
-    let watcher = setup_watcher(&paths.jobs_dir, event_tx)?;
+    let watcher = setup_watcher(&paths, event_tx)?;
 
-    let mut ticker = interval(Duration::from_secs(1));
+    // Measured on the monotonic clock so a sleep/wake or NTP step does not look like a stall.
+    let started = Instant::now();
+    let heartbeat = Arc::new(AtomicI64::new(0));
+    let watchdog = spawn_watchdog(paths.clone(), started, heartbeat.clone(), rt.tick());
+    let mut last_tick = (Instant::now(), Local::now());
+
+    let mut reload_pending_since: Option<Instant> = None;
+    rt.state_interval_seconds = Some(state_interval.max(1));
+    let state_interval = Duration::from_secs(state_interval.max(1));
+    let mut last_state_write = Instant::now();
+    let mut ticker = interval(rt.tick());
     let mut cleanup_tick = interval(Duration::from_secs(3600));
+    let mut sigint = signal(SignalKind::interrupt()).context("install SIGINT handler")?;
+    let mut sigterm = signal(SignalKind::terminate()).context("install SIGTERM handler")?;
+    let mut sighup = signal(SignalKind::hangup()).context("install SIGHUP handler")?;
 
     loop {
         tokio::select! {
             _ = ticker.tick() => {
-                let has_reload = drain_watcher(&event_rx);
+                heartbeat.store(started.elapsed().as_secs() as i64, Ordering::Relaxed);
+                check_clock_jump(&paths, &rt, &mut last_tick)?;
+                if drain_watcher(&paths, &event_rx) {
+                    reload_pending_since = Some(Instant::now());
+                }
+                let has_reload = reload_pending_since.is_some_and(|t| t.elapsed() >= RELOAD_DEBOUNCE);
                 if has_reload {
-                    match config::load_jobs(&paths.jobs_dir) {
-                        Ok(v) => {
-                            jobs = v;
-                            next_runs = compute_next_runs(&jobs);
-                            last_reload_error = None;
-                            logging::log_daemon(&paths.logs_dir, "INFO", "jobs reloaded")?;
+                    reload_pending_since = None;
+                    rt.dirty = true;
+                    match config::load_jobs_skipping_collisions(&paths) {
+                        Ok(loaded) => {
+                            log_schedule_warnings(&paths, &loaded.jobs)?;
+                            log_tick_warnings(&paths, &loaded.jobs, rt.tick())?;
+                            cleanup_run_markers(&paths, &loaded.jobs)?;
+                            rt.last_reload_error = log_skipped_files(&paths, &loaded)?;
+                            rt.jobs = loaded.jobs;
+                            rt.tick_warned.clear();
+                            rt.next_runs = compute_next_runs(&rt.jobs, Local::now());
+                            log_next_runs(&paths, &rt)?;
+                            logging::log_daemon(
+                                &paths.logs_dir,
+                                "INFO",
+                                &format!("jobs reloaded files={}", rt.jobs.len()),
+                            )?;
                         }
                         Err(err) => {
                             let msg = format!("reload failed: {err:#}");
-                            last_reload_error = Some(msg.clone());
+                            rt.last_reload_error = Some(msg.clone());
                             logging::log_daemon(&paths.logs_dir, "ERROR", &msg)?;
                         }
                     }
                 }
 
-                for job_id in collect_requests(&paths.requests_dir)? {
-                    if let Some(job) = jobs.iter().find(|j| j.id == job_id && j.enabled).cloned() {
-                        spawn_job(job, "manual", paths.clone(), tx_run.clone());
-                    }
-                }
-
-                let now = Local::now();
-                for job in &jobs {
-                    let should_run = match next_runs.get(&job.id).and_then(|t| *t) {
-                        Some(ts) => ts <= now,
-                        None => false,
-                    };
-                    if should_run {
-                        spawn_job(job.clone(), "schedule", paths.clone(), tx_run.clone());
-                        let next = scheduler::next_run_after(job, now + chrono::TimeDelta::seconds(1)).ok().flatten();
-                        next_runs.insert(job.id.clone(), next);
-                    }
-                }
+                run_tick(&paths, &mut rt, &tx_run, &mut rx_run)?;
 
-                while let Ok(record) = rx_run.try_recv() {
-                    last_result.insert(record.job_id.clone(), record.clone());
-                    recent_runs.push(record);
-                    if recent_runs.len() > 100 {
-                        let drop_count = recent_runs.len() - 100;
-                        recent_runs.drain(0..drop_count);
-                    }
+                if rt.dirty || last_state_write.elapsed() >= state_interval {
+                    write_state(&paths, std::process::id(), true, &rt)?;
+                    rt.dirty = false;
+                    last_state_write = Instant::now();
                 }
-
-                write_state(
-                    &paths,
-                    std::process::id(),
-                    &jobs,
-                    &next_runs,
-                    &last_result,
-                    &recent_runs,
-                    last_reload_error.clone(),
-                )?;
             }
             _ = cleanup_tick.tick() => {
                 logging::cleanup_old_logs(&paths.logs_dir, 30)?;
             }
-            _ = tokio::signal::ctrl_c() => {
+            _ = sigint.recv() => {
+                logging::log_daemon(&paths.logs_dir, "INFO", "received SIGINT")?;
                 break;
             }
+            _ = sigterm.recv() => {
+                logging::log_daemon(&paths.logs_dir, "INFO", "received SIGTERM")?;
+                break;
+            }
+            _ = sighup.recv() => {
+                logging::log_daemon(&paths.logs_dir, "INFO", "received SIGHUP, reloading jobs")?;
+                // Skip the debounce: the operator asked for the reload explicitly.
+                reload_pending_since = Some(Instant::now().checked_sub(RELOAD_DEBOUNCE).unwrap_or_else(Instant::now));
+            }
         }
     }
 
     drop(watcher);
+    watchdog.abort();
     logging::log_daemon(&paths.logs_dir, "INFO", "daemon stopped")?;
     Ok(())
 }
 
-pub async fn run_job_inline(paths: &AppPaths, job_id: &str) -> Result<ExecutionRecord> {
-    let jobs = config::load_jobs(&paths.jobs_dir)?;
+#[derive(Default)]
+struct Runtime {
+    jobs: Vec<JobConfig>,
+    next_runs: HashMap<String, Option<chrono::DateTime<Local>>>,
+    last_result: HashMap<String, ExecutionRecord>,
+    recent_runs: Vec<ExecutionRecord>,
+    last_reload_error: Option<String>,
+    paused: bool,
+    block_manual: bool,
+    tick_warned: HashSet<String>,
+    running_manual: HashMap<String, usize>,
+    running: HashMap<String, usize>,
+    consecutive_failures: HashMap<String, u32>,
+    duration_stats: HashMap<String, DurationStats>,
+    request_parse_failures: HashMap<PathBuf, u32>,
+    state_interval_seconds: Option<u64>,
+    tick_ms: Option<u64>,
+    daemon_started_at: Option<chrono::DateTime<Local>>,
+    // Slots due before this instant were missed while the daemon was down and are catch-up runs.
+    catchup_before: Option<chrono::DateTime<Local>>,
+    // Latest wall time a tick has scheduled up to; after the clock steps back, slots at or before
+    // it already ran (or were skipped) and must not fire again.
+    clock_high_water: Option<chrono::DateTime<Local>>,
+    dirty: bool,
+}
+
+impl Runtime {
+    fn tick(&self) -> Duration {
+        Duration::from_millis(self.tick_ms.unwrap_or(DEFAULT_TICK_MS))
+    }
+
+    fn restore_history(&mut self, state: DaemonState) {
+        self.daemon_started_at = state.daemon_started_at;
+        for record in &state.recent_runs {
+            self.count_failure(record);
+        }
+        self.recent_runs = state.recent_runs;
+        let excess = self.recent_runs.len().saturating_sub(history_size());
+        self.recent_runs.drain(..excess);
+        self.duration_stats = state
+            .jobs
+            .iter()
+            .filter_map(|j| j.duration.clone().map(|d| (j.id.clone(), d)))
+            .collect();
+        self.last_result = state
+            .jobs
+            .into_iter()
+            .filter_map(|j| j.last_result.map(|r| (j.id, r)))
+            .collect();
+    }
+
+    // Only successful runs feed the average: failures and timeouts stop early or at the limit.
+    // Returns the expected duration when the run is an anomaly for a job with slow_anomaly_factor.
+    fn update_duration_stats(&mut self, record: &ExecutionRecord) -> Option<f64> {
+        if record.status != "success" {
+            return None;
+        }
+        let actual = (record.ended_at - record.started_at).num_milliseconds() as f64 / 1000.0;
+        let factor = self
+            .jobs
+            .iter()
+            .find(|j| j.id == record.job_id)
+            .and_then(|j| j.slow_anomaly_factor);
+        let stats = self.duration_stats.entry(record.job_id.clone()).or_default();
+        let expected = stats.ema_seconds;
+        let anomaly = factor.is_some_and(|f| stats.samples >= DURATION_MIN_SAMPLES && actual > expected * f);
+        stats.ema_seconds = if stats.samples == 0 {
+            actual
+        } else {
+            DURATION_EMA_ALPHA * actual + (1.0 - DURATION_EMA_ALPHA) * expected
+        };
+        stats.samples += 1;
+        anomaly.then_some(expected)
+    }
+
+    fn push_record(&mut self, record: ExecutionRecord) {
+        self.dirty = true;
+        self.count_failure(&record);
+        self.last_result.insert(record.job_id.clone(), record.clone());
+        self.recent_runs.push(record);
+        if self.recent_runs.len() > history_size() {
+            let drop_count = self.recent_runs.len() - history_size();
+            self.recent_runs.drain(0..drop_count);
+        }
+    }
+
+    fn count_failure(&mut self, record: &ExecutionRecord) {
+        if record.status == "success" {
+            self.consecutive_failures.remove(&record.job_id);
+        } else {
+            *self.consecutive_failures.entry(record.job_id.clone()).or_default() += 1;
+        }
+    }
+}
+
+fn run_tick(
+    paths: &AppPaths,
+    rt: &mut Runtime,
+    tx_run: &mpsc::Sender<ExecutionRecord>,
+    rx_run: &mut mpsc::Receiver<ExecutionRecord>,
+) -> Result<()> {
+    let requests = collect_requests(paths, &mut rt.request_parse_failures)?;
+    let mut run_counts: HashMap<&str, usize> = HashMap::new();
+    // Deduped requests share one run, so every waiter of the job gets that run's result.
+    let mut waiters: HashMap<String, Vec<String>> = HashMap::new();
+    for request in &requests {
+        if let DaemonRequest::Run { job_id, wait_id } = request {
+            *run_counts.entry(job_id.as_str()).or_default() += 1;
+            if let Some(wait_id) = wait_id {
+                waiters.entry(job_id.clone()).or_default().push(wait_id.clone());
+            }
+        }
+    }
+    for (job_id, count) in run_counts.iter().filter(|(_, c)| **c > 1) {
+        logging::log_daemon(
+            &paths.logs_dir,
+            "INFO",
+            &format!("event=deduped job_id={job_id} count={count}"),
+        )?;
+    }
+    let mut seen_runs = HashSet::new();
+    for request in requests {
+        rt.dirty = true;
+        match request {
+            DaemonRequest::Run { job_id, .. } => {
+                if !seen_runs.insert(job_id.clone()) {
+                    continue;
+                }
+                let waiting = waiters.remove(&job_id).unwrap_or_default();
+                if rt.paused && rt.block_manual {
+                    logging::log_daemon(
+                        &paths.logs_dir,
+                        "INFO",
+                        &format!("manual run of {job_id} skipped: daemon paused"),
+                    )?;
+                    write_skipped_results(paths, &waiting, &job_id, "daemon paused");
+                    continue;
+                }
+                if let Some(job) = rt.jobs.iter().find(|j| j.id == job_id && j.enabled).cloned() {
+                    let running = rt.running_manual.entry(job_id.clone()).or_default();
+                    if job.max_manual_runs.is_some_and(|max| *running >= max as usize) {
+                        logging::log_daemon(
+                            &paths.logs_dir,
+                            "INFO",
+                            &format!(
+                                "manual run of {job_id} skipped: {running} manual runs already in progress"
+                            ),
+                        )?;
+                        let reason = format!("{running} manual runs already in progress");
+                        write_skipped_results(paths, &waiting, &job_id, &reason);
+                        continue;
+                    }
+                    let Some(lock) = acquire_job_lock(paths, &job)? else {
+                        logging::log_daemon(
+                            &paths.logs_dir,
+                            "INFO",
+                            &format!("manual run of {job_id} skipped: previous run still in progress (allow_overlap=false)"),
+                        )?;
+                        let reason = "previous run still in progress (allow_overlap=false)";
+                        write_skipped_results(paths, &waiting, &job_id, reason);
+                        continue;
+                    };
+                    *running += 1;
+                    *rt.running.entry(job_id.clone()).or_default() += 1;
+                    spawn_job(job, "manual", paths.clone(), tx_run.clone(), lock, waiting);
+                } else {
+                    logging::log_daemon(
+                        &paths.logs_dir,
+                        "DEBUG",
+                        &format!("manual run of {job_id} ignored: job not found or disabled"),
+                    )?;
+                    write_skipped_results(paths, &waiting, &job_id, "job not found or disabled");
+                }
+            }
+            DaemonRequest::Pause { block_manual } => {
+                rt.paused = true;
+                rt.block_manual = block_manual;
+                logging::log_daemon(
+                    &paths.logs_dir,
+                    "INFO",
+                    &format!("daemon paused block_manual={block_manual}"),
+                )?;
+            }
+            DaemonRequest::Resume => {
+                rt.paused = false;
+                rt.block_manual = false;
+                logging::log_daemon(&paths.logs_dir, "INFO", "daemon resumed")?;
+            }
+            DaemonRequest::Record { record } => handle_record(paths, rt, *record)?,
+        }
+    }
+
+    let now = Local::now();
+    let mut started = Vec::new();
+    for job in &rt.jobs {
+        let due = rt.next_runs.get(&job.id).copied().flatten().filter(|ts| *ts <= now);
+        if let Some(due) = due {
+            logging::log_daemon(
+                &paths.logs_dir,
+                "DEBUG",
+                &format!("job={} due={} paused={}", job.id, due.format("%Y-%m-%d %H:%M:%S%:z"), rt.paused),
+            )?;
+            // A slot at or before the high-water mark already ran; only one past it may still fire.
+            let slot = match rt.clock_high_water {
+                Some(at) if due <= at => scheduler::next_run_after(job, at).ok().flatten().filter(|next| *next <= now),
+                _ => Some(due),
+            };
+            let stepped_back = slot.is_none();
+            let due = slot.unwrap_or(due);
+            if stepped_back {
+                logging::log_daemon(
+                    &paths.logs_dir,
+                    "INFO",
+                    &format!(
+                        "job={} event=skipped reason=clock-stepped-back due={}",
+                        job.id,
+                        due.format("%Y-%m-%d %H:%M:%S%:z")
+                    ),
+                )?;
+            } else if rt.paused {
+                logging::log_daemon(
+                    &paths.logs_dir,
+                    "INFO",
+                    &format!("scheduled run of {} skipped: daemon paused", job.id),
+                )?;
+            } else if !scheduler::in_active_window(job, now) {
+                logging::log_daemon(
+                    &paths.logs_dir,
+                    "INFO",
+                    &format!(
+                        "job={} event=skipped reason=outside-window due={}",
+                        job.id,
+                        due.format("%Y-%m-%d %H:%M:%S%:z")
+                    ),
+                )?;
+            } else if rt.catchup_before.is_some_and(|start| due < start)
+                && let Some(last) = last_success_at(paths, &job.id).filter(|at| *at >= due)
+            {
+                logging::log_daemon(
+                    &paths.logs_dir,
+                    "INFO",
+                    &format!(
+                        "job={} event=catchup-skipped reason=already-ran due={} last_success={}",
+                        job.id,
+                        due.format("%Y-%m-%d %H:%M:%S%:z"),
+                        last.format("%Y-%m-%d %H:%M:%S%:z")
+                    ),
+                )?;
+            } else if let Some(lock) = acquire_job_lock(paths, job)? {
+                spawn_job(job.clone(), "schedule", paths.clone(), tx_run.clone(), lock, Vec::new());
+                started.push(job.id.clone());
+            } else {
+                logging::log_daemon(
+                    &paths.logs_dir,
+                    "INFO",
+                    &format!("scheduled run of {} skipped: previous run still in progress (allow_overlap=false)", job.id),
+                )?;
+            }
+            let mut next = scheduler::next_run_after(job, now).ok().flatten();
+            if next.is_some_and(|at| at <= now) {
+                if rt.tick_warned.insert(job.id.clone()) {
+                    logging::log_daemon(
+                        &paths.logs_dir,
+                        "WARN",
+                        &format!(
+                            "job={} schedule fires faster than the {}ms daemon tick, skipping slots until the next tick",
+                            job.id,
+                            rt.tick().as_millis()
+                        ),
+                    )?;
+                }
+                let after = now + chrono::TimeDelta::from_std(rt.tick())?;
+                next = scheduler::next_run_after(job, after).ok().flatten();
+            }
+            logging::log_daemon(
+                &paths.logs_dir,
+                "DEBUG",
+                &format!(
+                    "job={} next_run={}",
+                    job.id,
+                    next.map(|t| t.format("%Y-%m-%d %H:%M:%S%:z").to_string()).unwrap_or_else(|| "-".to_string())
+                ),
+            )?;
+            rt.next_runs.insert(job.id.clone(), next);
+            rt.dirty = true;
+        }
+    }
+
+    for job_id in started {
+        *rt.running.entry(job_id).or_default() += 1;
+    }
+    rt.clock_high_water = Some(rt.clock_high_water.map_or(now, |at| at.max(now)));
+
+    while let Ok(record) = rx_run.try_recv() {
+        handle_run_record(paths, rt, record)?;
+    }
+    Ok(())
+}
+
+// Records from spawned runs; inline runs report through DaemonRequest::Record and were never counted.
+fn handle_run_record(paths: &AppPaths, rt: &mut Runtime, record: ExecutionRecord) -> Result<()> {
+    if let Some(running) = rt.running.get_mut(&record.job_id) {
+        *running = running.saturating_sub(1);
+    }
+    if record.status == "success"
+        && let Some(url) = rt.jobs.iter().find(|j| j.id == record.job_id).and_then(|j| j.ping_url_on_success.clone())
+    {
+        spawn_ping(paths.clone(), record.job_id.clone(), record.run_id.clone(), "success", url);
+    }
+    handle_record(paths, rt, record)
+}
+
+fn handle_record(paths: &AppPaths, rt: &mut Runtime, record: ExecutionRecord) -> Result<()> {
+    if record.trigger == "manual"
+        && let Some(running) = rt.running_manual.get_mut(&record.job_id)
+    {
+        *running = running.saturating_sub(1);
+    }
+    if let Err(err) = write_run_marker(paths, &record) {
+        logging::log_daemon(&paths.logs_dir, "ERROR", &format!("write run marker failed: {err:#}"))?;
+    }
+    if let Some(expected) = rt.update_duration_stats(&record) {
+        let message = log_slow_anomaly(paths, &record, expected)?;
+        if let Some(job) = rt.jobs.iter().find(|j| j.id == record.job_id && j.on_slow.is_some()) {
+            let anomaly = ExecutionRecord {
+                status: "slow-anomaly".to_string(),
+                message,
+                ..record.clone()
+            };
+            spawn_hook(paths.clone(), job.clone(), anomaly);
+        }
+    }
+    rt.push_record(record.clone());
+    auto_disable_if_failing(paths, rt, &record)?;
+    complete_once_job(paths, rt, &record)
+}
+
+// A fired one-shot would otherwise stay listed forever with no next run.
+fn complete_once_job(paths: &AppPaths, rt: &mut Runtime, record: &ExecutionRecord) -> Result<()> {
+    if record.trigger != "schedule" || record.status != "success" {
+        return Ok(());
+    }
+    let Some(pos) = rt.jobs.iter().position(|j| {
+        j.id == record.job_id
+            && j.enabled
+            && matches!(j.schedule, ScheduleConfig::Simple { repeat: Repeat::Once, .. })
+    }) else {
+        return Ok(());
+    };
+    let job = &rt.jobs[pos];
+    let (action, result) = if paths.config_file.is_some() {
+        ("disabled-until-reload", Ok(()))
+    } else if job.delete_after_run {
+        ("deleted", config::remove_job_file(&config::job_file_path(&paths.jobs_dir, &job.id)))
+    } else {
+        ("disabled", config::mark_job_completed(&paths.jobs_dir, &job.id, record.ended_at))
+    };
+    if let Err(err) = result {
+        logging::log_daemon(
+            &paths.logs_dir,
+            "ERROR",
+            &format!("completing one-shot job {} failed: {err:#}", job.id),
+        )?;
+        return Ok(());
+    }
+    logging::log_job(
+        &paths.logs_dir,
+        "INFO",
+        &record.job_id,
+        &record.run_id,
+        &format!("event=completed action={action}"),
+    )?;
+    if job.delete_after_run && paths.config_file.is_none() {
+        rt.jobs.remove(pos);
+        rt.next_runs.remove(&record.job_id);
+    } else {
+        rt.jobs[pos].enabled = false;
+        rt.jobs[pos].completed_at = Some(record.ended_at);
+        rt.next_runs.insert(record.job_id.clone(), None);
+    }
+    rt.dirty = true;
+    Ok(())
+}
+
+fn log_slow_anomaly(paths: &AppPaths, record: &ExecutionRecord, expected: f64) -> Result<String> {
+    let actual = (record.ended_at - record.started_at).num_milliseconds() as f64 / 1000.0;
+    let message = format!("event=slow-anomaly expected={expected:.1}s actual={actual:.1}s");
+    logging::log_job(&paths.logs_dir, "WARN", &record.job_id, &record.run_id, &message)?;
+    Ok(message)
+}
+
+fn write_run_marker(paths: &AppPaths, record: &ExecutionRecord) -> Result<()> {
+    let dir = if record.status == "success" {
+        &paths.last_success_dir
+    } else {
+        &paths.last_failure_dir
+    };
+    let content = format!(
+        "run_id={}\nstatus={}\nstarted_at={}\nended_at={}\n",
+        record.run_id,
+        record.status,
+        record.started_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        record.ended_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+    );
+    paths::write_file(&dir.join(&record.job_id), content)?;
+    Ok(())
+}
+
+fn last_success_at(paths: &AppPaths, job_id: &str) -> Option<chrono::DateTime<Local>> {
+    let raw = std::fs::read_to_string(paths.last_success_dir.join(job_id)).ok()?;
+    let value = raw.lines().find_map(|line| line.strip_prefix("started_at="))?;
+    let at = chrono::DateTime::parse_from_rfc3339(value).ok()?;
+    Some(at.with_timezone(&Local))
+}
+
+fn cleanup_run_markers(paths: &AppPaths, jobs: &[JobConfig]) -> Result<()> {
+    let ids: HashSet<&str> = jobs.iter().map(|j| j.id.as_str()).collect();
+    for dir in [&paths.last_success_dir, &paths.last_failure_dir] {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            if !ids.contains(name.to_string_lossy().as_ref()) {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn auto_disable_if_failing(paths: &AppPaths, rt: &mut Runtime, record: &ExecutionRecord) -> Result<()> {
+    let failures = rt.consecutive_failures.get(&record.job_id).copied().unwrap_or(0);
+    let Some(job) = rt.jobs.iter_mut().find(|j| j.id == record.job_id) else {
+        return Ok(());
+    };
+    if !job.enabled || job.disable_after_failures.is_none_or(|limit| failures < limit) {
+        return Ok(());
+    }
+
+    // A --config file is never rewritten, so the job stays disabled until the next reload.
+    if paths.config_file.is_none()
+        && let Err(err) = config::set_job_enabled(&paths.jobs_dir, &job.id, false)
+    {
+        logging::log_daemon(
+            &paths.logs_dir,
+            "ERROR",
+            &format!("auto-disable of {} failed: {err:#}", job.id),
+        )?;
+        return Ok(());
+    }
+    job.enabled = false;
+    rt.next_runs.insert(job.id.clone(), None);
+    rt.consecutive_failures.remove(&job.id);
+    logging::log_job(
+        &paths.logs_dir,
+        "ERROR",
+        &job.id,
+        &record.run_id,
+        &format!("event=auto-disabled consecutive_failures={failures}"),
+    )?;
+
+    let record = ExecutionRecord {
+        status: "auto-disabled".to_string(),
+        ..record.clone()
+    };
+    spawn_hook(paths.clone(), job.clone(), record);
+    Ok(())
+}
+
+enum DaemonRequest {
+    // `wait_id` names the file in `run/results/` a waiting `run --wait` polls for.
+    Run { job_id: String, wait_id: Option<String> },
+    Pause { block_manual: bool },
+    Resume,
+    Record { record: Box<ExecutionRecord> },
+}
+
+pub async fn run_job_inline(paths: &AppPaths, job_id: &str, show_output: bool) -> Result<ExecutionRecord> {
+    let jobs = config::load_jobs_skipping_collisions(paths)?.jobs;
     let job = jobs
         .into_iter()
         .find(|j| j.id == job_id)
-        .ok_or_else(|| anyhow!("job not found: {job_id}"))?;
+        .ok_or_else(|| MacrondError::JobNotFound(job_id.to_string()))?;
 
-    execute_job(paths.clone(), job, "manual-inline").await
+    let Some(_lock) = acquire_job_lock(paths, &job)? else {
+        return Err(MacrondError::JobRunning {
+            job_id: job_id.to_string(),
+            lock: paths.locks_dir.join(format!("{job_id}.lock")),
+        }
+        .into());
+    };
+    let record = execute_job(paths.clone(), job, "manual-inline", show_output).await?;
+    record_inline_run(paths, &record)?;
+    Ok(record)
 }
 
-fn compute_next_runs(jobs: &[JobConfig]) -> HashMap<String, Option<chrono::DateTime<Local>>> {
+fn record_inline_run(paths: &AppPaths, record: &ExecutionRecord) -> Result<()> {
+    if daemon_running(paths)?.is_some() {
+        let payload = serde_json::json!({ "action": "record", "record": record });
+        return write_request(paths, &Uuid::new_v4().to_string(), &payload);
+    }
+
+    let mut rt = Runtime {
+        jobs: config::load_jobs_skipping_collisions(paths)?.jobs,
+        ..Runtime::default()
+    };
+    rt.next_runs = compute_next_runs(&rt.jobs, Local::now());
+    if let Ok(state) = read_state(paths) {
+        rt.last_reload_error = state.last_reload_error.clone();
+        rt.restore_history(state);
+    }
+    write_run_marker(paths, record)?;
+    if let Some(expected) = rt.update_duration_stats(record) {
+        log_slow_anomaly(paths, record, expected)?;
+    }
+    rt.push_record(record.clone());
+    write_state(paths, 0, false, &rt)
+}
+
+fn log_skipped_files(paths: &AppPaths, loaded: &LoadedJobs) -> Result<Option<String>> {
+    for collision in &loaded.collisions {
+        logging::log_daemon(
+            &paths.logs_dir,
+            "WARN",
+            &format!("skipping job file {}: {collision}", collision.skipped.display()),
+        )?;
+    }
+    for rejected in &loaded.rejected {
+        logging::log_daemon(
+            &paths.logs_dir,
+            "WARN",
+            &format!("skipping job file {}: {rejected}", rejected.path.display()),
+        )?;
+    }
+    Ok(config::describe_skipped(&paths.jobs_dir, loaded))
+}
+
+fn log_schedule_warnings(paths: &AppPaths, jobs: &[JobConfig]) -> Result<()> {
     let now = Local::now();
+    for job in jobs {
+        for warning in config::schedule_warnings(job, now) {
+            logging::log_daemon(&paths.logs_dir, "WARN", &format!("job={} {warning}", job.id))?;
+        }
+    }
+    Ok(())
+}
+
+// Compares the tick with the gap between a job's next two runs, the finest interval it needs.
+fn log_tick_warnings(paths: &AppPaths, jobs: &[JobConfig], tick: Duration) -> Result<()> {
+    let now = Local::now();
+    for job in jobs {
+        let Some(first) = scheduler::next_run_after(job, now).ok().flatten() else {
+            continue;
+        };
+        let Some(second) = scheduler::next_run_after(job, first).ok().flatten() else {
+            continue;
+        };
+        let gap = (second - first).to_std().unwrap_or_default();
+        if gap <= tick {
+            logging::log_daemon(
+                &paths.logs_dir,
+                "WARN",
+                &format!(
+                    "job={} runs every {}s but the daemon tick is {}ms, runs may be late or skipped; lower --tick-ms",
+                    job.id,
+                    gap.as_secs(),
+                    tick.as_millis()
+                ),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn log_next_runs(paths: &AppPaths, rt: &Runtime) -> Result<()> {
+    for job in &rt.jobs {
+        let decision = match rt.next_runs.get(&job.id).copied().flatten() {
+            Some(next) => format!("next_run={}", next.format("%Y-%m-%d %H:%M:%S%:z")),
+            None if !job.enabled => "not scheduled: disabled".to_string(),
+            None if job.manual_only => "not scheduled: manual_only".to_string(),
+            None => "not scheduled: no future run".to_string(),
+        };
+        logging::log_daemon(
+            &paths.logs_dir,
+            "DEBUG",
+            &format!("job={} schedule={} {decision}", job.id, scheduler::schedule_label(job)),
+        )?;
+    }
+    Ok(())
+}
+
+// The monotonic clock stops while the machine sleeps and ignores NTP steps, so wall time moving
+// further (or backwards) than it between ticks means the clock jumped. Forward jumps need nothing
+// special: each job whose slot passed runs once and the slots in between are not replayed.
+// Backward jumps are handled in run_tick, which skips slots at or before `clock_high_water`.
+fn check_clock_jump(paths: &AppPaths, rt: &Runtime, last_tick: &mut (Instant, chrono::DateTime<Local>)) -> Result<()> {
+    let (instant, wall) = (Instant::now(), Local::now());
+    let elapsed = chrono::TimeDelta::from_std(instant.duration_since(last_tick.0))?;
+    let drift = (wall - last_tick.1) - elapsed;
+    *last_tick = (instant, wall);
+    if drift.num_seconds().abs() < CLOCK_JUMP_SECONDS {
+        return Ok(());
+    }
+    let message = if drift > chrono::TimeDelta::zero() {
+        let due = rt.next_runs.values().flatten().filter(|at| **at <= wall).count();
+        format!("event=clock-jump direction=forward seconds={} due_jobs={due}", drift.num_seconds())
+    } else {
+        let skip_until = rt.clock_high_water.unwrap_or(wall);
+        format!(
+            "event=clock-jump direction=backward seconds={} skip_until={}",
+            -drift.num_seconds(),
+            skip_until.format("%Y-%m-%d %H:%M:%S%:z")
+        )
+    };
+    logging::log_daemon(&paths.logs_dir, "WARN", &message)
+}
+
+// Runs on its own task so it keeps reporting while the scheduler loop is blocked.
+fn spawn_watchdog(
+    paths: AppPaths,
+    started: Instant,
+    heartbeat: Arc<AtomicI64>,
+    tick: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = interval(tick);
+        let mut stalled = false;
+        loop {
+            ticker.tick().await;
+            let age = started.elapsed().as_secs() as i64 - heartbeat.load(Ordering::Relaxed);
+            if age >= stall_after(tick).as_secs() as i64 {
+                if !stalled {
+                    stalled = true;
+                    let _ = logging::log_daemon(
+                        &paths.logs_dir,
+                        "ERROR",
+                        &format!("event=stalled seconds={age} scheduler loop has not ticked"),
+                    );
+                }
+            } else if stalled {
+                stalled = false;
+                let _ = logging::log_daemon(&paths.logs_dir, "INFO", "event=recovered scheduler loop ticking again");
+            }
+        }
+    })
+}
+
+// The heartbeat has one-second resolution, so sub-second ticks still get a few seconds of slack.
+fn stall_after(tick: Duration) -> Duration {
+    (tick * STALL_TICKS).max(Duration::from_secs(STALL_TICKS.into()))
+}
+
+/// Age after which a running daemon's state file counts as stale (loop likely stuck).
+pub fn state_stale_after(state: &DaemonState) -> Option<chrono::TimeDelta> {
+    let interval = state.state_interval_seconds?;
+    let grace = stall_after(Duration::from_millis(state.tick_ms.unwrap_or(DEFAULT_TICK_MS))).as_secs();
+    Some(chrono::TimeDelta::seconds((interval + grace) as i64))
+}
+
+fn compute_next_runs(
+    jobs: &[JobConfig],
+    after: chrono::DateTime<Local>,
+) -> HashMap<String, Option<chrono::DateTime<Local>>> {
     let mut map = HashMap::new();
     for job in jobs {
-        let next = scheduler::next_run_after(job, now).ok().flatten();
+        let next = scheduler::next_run_after(job, after).ok().flatten();
         map.insert(job.id.clone(), next);
     }
     map
 }
 
+// A persisted slot is kept while the current schedule still produces it, so future times stay
+// put across restarts. Past slots are only kept for one-shot jobs, which would otherwise be
+// lost; recurring jobs keep the time recomputed from now.
+fn restore_next_runs(
+    paths: &AppPaths,
+    rt: &mut Runtime,
+    persisted: &HashMap<String, chrono::DateTime<Local>>,
+    now: chrono::DateTime<Local>,
+) -> Result<()> {
+    for job in &rt.jobs {
+        let Some(&at) = persisted.get(&job.id) else {
+            continue;
+        };
+        let still_scheduled =
+            scheduler::next_run_after(job, at - chrono::TimeDelta::seconds(1)).ok().flatten() == Some(at);
+        let once = matches!(job.schedule, ScheduleConfig::Simple { repeat: Repeat::Once, .. });
+        // An earlier computed slot (a `--once` pass catching up since the last state write) wins.
+        let computed = rt.next_runs.get(&job.id).copied().flatten();
+        if !still_scheduled || (at <= now && !once) || computed.is_some_and(|c| c < at) {
+            continue;
+        }
+        if at <= now && computed != Some(at) {
+            logging::log_daemon(
+                &paths.logs_dir,
+                "INFO",
+                &format!("job={} restored missed one-shot run due={}", job.id, at.format("%Y-%m-%d %H:%M:%S%:z")),
+            )?;
+        }
+        rt.next_runs.insert(job.id.clone(), Some(at));
+    }
+    Ok(())
+}
+
 fn setup_watcher(
-    jobs_dir: &Path,
+    paths: &AppPaths,
     event_tx: std::sync::mpsc::Sender<notify::Result<notify::Event>>,
 ) -> Result<RecommendedWatcher> {
     let mut watcher = notify::recommended_watcher(move |res| {
         let _ = event_tx.send(res);
     })?;
-    watcher.watch(jobs_dir, RecursiveMode::NonRecursive)?;
+    match &paths.config_file {
+        // The directory, not the file: editors and config mounts replace the file by renaming
+        // (or swapping a symlink), which a watch on the old inode would miss.
+        Some(file) => watcher.watch(file.parent().unwrap_or(Path::new("/")), RecursiveMode::NonRecursive)?,
+        None => {
+            watcher.watch(&paths.jobs_dir, RecursiveMode::Recursive)?;
+            watcher.watch(&paths.base_dir, RecursiveMode::NonRecursive)?;
+        }
+    }
     Ok(watcher)
 }
 
-fn drain_watcher(event_rx: &std::sync::mpsc::Receiver<notify::Result<notify::Event>>) -> bool {
+fn drain_watcher(paths: &AppPaths, event_rx: &std::sync::mpsc::Receiver<notify::Result<notify::Event>>) -> bool {
     let mut changed = false;
     while let Ok(event) = event_rx.try_recv() {
-        if event.is_ok() {
+        if let Ok(event) = event
+            && !matches!(event.kind, EventKind::Access(_))
+            && event.paths.iter().any(|p| match &paths.config_file {
+                Some(file) => is_config_file_event(file, p),
+                None => is_job_file(p),
+            })
+        {
             changed = true;
         }
     }
     changed
 }
 
-fn collect_requests(requests_dir: &Path) -> Result<Vec<String>> {
+// The config file's directory is watched, but only the file, its checksum sidecar, or the
+// `..data` symlink a Kubernetes ConfigMap mount swaps on update count as a change.
+fn is_config_file_event(config_file: &Path, path: &Path) -> bool {
+    let Some(name) = path.file_name() else {
+        return false;
+    };
+    name == "..data"
+        || Some(name) == config_file.file_name()
+        || Some(name) == config::checksum_path(config_file).file_name()
+}
+
+fn is_job_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|s| s.to_str()),
+        Some("json") | Some("yaml") | Some("yml") | Some("sha256")
+    )
+}
+
+// A file that does not parse may still be mid-write by a writer that does not rename into
+// place, so it is left alone for a few ticks before being dropped.
+fn collect_requests(paths: &AppPaths, parse_failures: &mut HashMap<PathBuf, u32>) -> Result<Vec<DaemonRequest>> {
     let mut requests = Vec::new();
+    let mut pending = HashMap::new();
 
-    for entry in std::fs::read_dir(requests_dir)? {
+    for entry in std::fs::read_dir(&paths.requests_dir)? {
         let entry = entry?;
         let path = entry.path();
         if !path.is_file() {
@@ -181,63 +994,197 @@ fn collect_requests(requests_dir: &Path) -> Result<Vec<String>> {
             continue;
         }
 
-        let raw = std::fs::read_to_string(&path)?;
+        let Ok(raw) = std::fs::read_to_string(&path) else {
+            continue;
+        };
         #[derive(serde::Deserialize)]
         struct Req {
-            job_id: String,
+            #[serde(default)]
+            job_id: Option<String>,
+            #[serde(default)]
+            action: Option<String>,
+            #[serde(default)]
+            block_manual: bool,
+            #[serde(default)]
+            wait: bool,
+            #[serde(default)]
+            record: Option<ExecutionRecord>,
         }
-        if let Ok(req) = serde_json::from_str::<Req>(&raw) {
-            requests.push(req.job_id);
+        let req = match serde_json::from_str::<Req>(&raw) {
+            Ok(req) => req,
+            Err(err) => {
+                let attempts = parse_failures.get(&path).copied().unwrap_or(0) + 1;
+                if attempts < REQUEST_PARSE_ATTEMPTS {
+                    pending.insert(path, attempts);
+                } else {
+                    logging::log_daemon(
+                        &paths.logs_dir,
+                        "WARN",
+                        &format!("dropping unreadable request {} after {attempts} attempts: {err}", path.display()),
+                    )?;
+                    let _ = std::fs::remove_file(path);
+                }
+                continue;
+            }
+        };
+        match (req.action.as_deref(), req.job_id) {
+            (Some("pause"), _) => requests.push(DaemonRequest::Pause {
+                block_manual: req.block_manual,
+            }),
+            (Some("resume"), _) => requests.push(DaemonRequest::Resume),
+            (Some("record"), _) => {
+                if let Some(record) = req.record {
+                    requests.push(DaemonRequest::Record {
+                        record: Box::new(record),
+                    });
+                }
+            }
+            (None, Some(job_id)) => {
+                let wait_id = req
+                    .wait
+                    .then(|| path.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+                    .flatten();
+                requests.push(DaemonRequest::Run { job_id, wait_id });
+            }
+            _ => {}
         }
         let _ = std::fs::remove_file(path);
     }
 
+    *parse_failures = pending;
     Ok(requests)
 }
 
-fn spawn_job(job: JobConfig, trigger: &'static str, paths: AppPaths, tx: mpsc::Sender<ExecutionRecord>) {
+/// Per-job lock for jobs with `allow_overlap: false`, held until the run finishes. It is
+/// an flock on `run/locks/<job_id>.lock`, so inline runs in other processes see it too and
+/// it is released automatically if the holder dies.
+struct JobLock {
+    _flock: Option<Flock<std::fs::File>>,
+}
+
+/// Returns `None` when another run of the job holds the lock.
+fn acquire_job_lock(paths: &AppPaths, job: &JobConfig) -> Result<Option<JobLock>> {
+    if job.allow_overlap {
+        return Ok(Some(JobLock { _flock: None }));
+    }
+    let path = paths.locks_dir.join(format!("{}.lock", job.id));
+    let file = paths::open_file(&path, OpenOptions::new().create(true).truncate(false).write(true))
+        .with_context(|| format!("open lock file {}", path.display()))?;
+    match Flock::lock(file, FlockArg::LockExclusiveNonblock) {
+        Ok(lock) => Ok(Some(JobLock { _flock: Some(lock) })),
+        Err((_, Errno::EWOULDBLOCK)) => Ok(None),
+        Err((_, errno)) => Err(anyhow!("lock {}: {errno}", path.display())),
+    }
+}
+
+pub fn job_lock_held(paths: &AppPaths, job: &JobConfig) -> Result<bool> {
+    if !paths.locks_dir.exists() {
+        return Ok(false);
+    }
+    Ok(acquire_job_lock(paths, job)?.is_none())
+}
+
+fn spawn_job(
+    job: JobConfig,
+    trigger: &'static str,
+    paths: AppPaths,
+    tx: mpsc::Sender<ExecutionRecord>,
+    lock: JobLock,
+    waiting: Vec<String>,
+) {
     tokio::spawn(async move {
-        match execute_job(paths.clone(), job, trigger).await {
+        let _lock = lock;
+        match execute_job(paths.clone(), job.clone(), trigger, false).await {
             Ok(record) => {
+                if let Err(err) = run_hook(&paths, &job, &record).await {
+                    let _ = logging::log_daemon(&paths.logs_dir, "ERROR", &format!("run_hook failed: {err:#}"));
+                }
+                write_run_results(&paths, &waiting, &record);
                 let _ = tx.send(record).await;
             }
             Err(err) => {
                 let _ = logging::log_daemon(&paths.logs_dir, "ERROR", &format!("execute_job failed: {err:#}"));
+                // Still record the run so a job that cannot be executed shows up in history.
+                let now = Local::now();
+                let record = ExecutionRecord {
+                    run_id: Uuid::new_v4().to_string(),
+                    job_id: job.id.clone(),
+                    trigger: trigger.to_string(),
+                    started_at: now,
+                    ended_at: now,
+                    status: "failed".to_string(),
+                    exit_code: None,
+                    signal: None,
+                    hostname: current_hostname(),
+                    user: current_user(),
+                    message: format!("event=failed stage=execute message=execute-error:{err:#}"),
+                    output_tail: Vec::new(),
+                };
+                let _ = logging::log_job(&paths.logs_dir, "ERROR", &job.id, &record.run_id, &record.message);
+                if let Err(err) = run_hook(&paths, &job, &record).await {
+                    let _ = logging::log_daemon(&paths.logs_dir, "ERROR", &format!("run_hook failed: {err:#}"));
+                }
+                write_run_results(&paths, &waiting, &record);
+                let _ = tx.send(record).await;
             }
         }
     });
 }
 
-async fn execute_job(paths: AppPaths, job: JobConfig, trigger: &str) -> Result<ExecutionRecord> {
+async fn execute_job(
+    paths: AppPaths,
+    job: JobConfig,
+    trigger: &str,
+    show_output: bool,
+) -> Result<ExecutionRecord> {
     let run_id = Uuid::new_v4().to_string();
     let started_at = Local::now();
-    let (mut command, command_line) = build_command(&job);
+    let expanded = prepare_command(&paths.base_dir, &job.command, started_at);
+    let (mut command, command_line) = build_command(expanded.as_ref().unwrap_or(&job.command));
+    let hostname = current_hostname();
+    let user = current_user();
 
-    logging::log_job(
-        &paths.logs_dir,
-        "INFO",
-        &job.id,
-        &run_id,
-        &format!(
-            "event=start trigger={trigger} command=\"{command_line}\" timeout_seconds={}",
-            job.timeout_seconds
-        ),
-    )?;
+    if job.log_successes {
+        logging::log_job(
+            &paths.logs_dir,
+            "INFO",
+            &job.id,
+            &run_id,
+            &format!(
+                "event=start trigger={trigger} command=\"{command_line}\" timeout_seconds={} host={} user={}",
+                job.timeout_seconds,
+                hostname.as_deref().unwrap_or("-"),
+                user.as_deref().unwrap_or("-"),
+            ),
+        )?;
+    }
+    if trigger != "manual-inline"
+        && let Some(url) = &job.ping_url_on_start
+    {
+        spawn_ping(paths.clone(), job.id.clone(), run_id.clone(), "start", url.clone());
+    }
 
     command.stdin(Stdio::null());
-    command.stdout(Stdio::null());
-    command.stderr(Stdio::null());
-    if let Some(working_dir) = &job.command.working_dir {
-        command.current_dir(working_dir);
+    let tail_lines = job.output_tail_lines.unwrap_or(0);
+    if show_output {
+        command.stdout(Stdio::inherit());
+        command.stderr(Stdio::inherit());
+    } else {
+        command.stdout(Stdio::null());
+        command.stderr(Stdio::null());
+    }
+    if tail_lines > 0 {
+        command.stdout(Stdio::piped());
     }
-    command.envs(&job.command.env);
 
-    let timeout = Duration::from_secs(job.timeout_seconds.max(1));
-    let mut child = match command.spawn() {
+    let spawned = expanded
+        .and_then(|config| configure_process(&mut command, &config, &paths.base_dir))
+        .and_then(|()| Ok(command.spawn()?));
+    let mut child = match spawned {
         Ok(child) => child,
         Err(err) => {
             let ended_at = Local::now();
-            let message = format!("event=failed stage=spawn command=\"{command_line}\" error={err}");
+            let message = format!("event=failed stage=spawn command=\"{command_line}\" message=spawn-error:{err:#}");
             logging::log_job(&paths.logs_dir, "ERROR", &job.id, &run_id, &message)?;
             return Ok(ExecutionRecord {
                 run_id,
@@ -247,26 +1194,79 @@ async fn execute_job(paths: AppPaths, job: JobConfig, trigger: &str) -> Result<E
                 ended_at,
                 status: "failed".to_string(),
                 exit_code: None,
+                signal: None,
+                hostname,
+                user,
                 message,
+                output_tail: Vec::new(),
             });
         }
     };
+    let output_tail = Arc::new(Mutex::new(VecDeque::new()));
+    let mut tail_reader = child
+        .stdout
+        .take()
+        .map(|stdout| spawn_output_tail(stdout, tail_lines, show_output, output_tail.clone()));
 
-    let (status, exit_code, message) = match tokio::time::timeout(timeout, child.wait()).await {
+    let waited = {
+        let wait = child.wait();
+        let hard_timeout = sleep_secs(job.timeout_seconds);
+        let warn_timeout = sleep_secs(job.warn_after_seconds.unwrap_or(0));
+        tokio::pin!(wait, hard_timeout, warn_timeout);
+        let mut warned = false;
+        loop {
+            tokio::select! {
+                exit = &mut wait => break Ok(exit),
+                _ = &mut hard_timeout => break Err(()),
+                _ = &mut warn_timeout, if !warned => {
+                    warned = true;
+                    let elapsed = (Local::now() - started_at).num_seconds();
+                    logging::log_job(&paths.logs_dir, "WARN", &job.id, &run_id, &format!("event=slow elapsed={elapsed}"))?;
+                    if job.on_slow.is_some() {
+                        let record = ExecutionRecord {
+                            run_id: run_id.clone(),
+                            job_id: job.id.clone(),
+                            trigger: trigger.to_string(),
+                            started_at,
+                            ended_at: Local::now(),
+                            status: "slow".to_string(),
+                            exit_code: None,
+                            signal: None,
+                            hostname: hostname.clone(),
+                            user: user.clone(),
+                            message: format!("event=slow elapsed={elapsed}"),
+                            output_tail: Vec::new(),
+                        };
+                        spawn_hook(paths.clone(), job.clone(), record);
+                    }
+                }
+            }
+        }
+    };
+    let (status, exit_code, signal, message) = match waited {
         Ok(Ok(exit)) => {
             if exit.success() {
                 (
                     "success".to_string(),
                     exit.code(),
+                    None,
                     format!(
                         "event=success command=\"{command_line}\" exit_code={}",
                         exit.code().unwrap_or(0)
                     ),
                 )
+            } else if let Some(signal) = exit.signal().map(signal_name) {
+                (
+                    "failed".to_string(),
+                    None,
+                    Some(signal.clone()),
+                    format!("event=failed command=\"{command_line}\" signal={signal}"),
+                )
             } else {
                 (
                     "failed".to_string(),
                     exit.code(),
+                    None,
                     format!(
                         "event=failed command=\"{command_line}\" exit_code={}",
                         exit.code().unwrap_or(-1)
@@ -277,21 +1277,37 @@ async fn execute_job(paths: AppPaths, job: JobConfig, trigger: &str) -> Result<E
         Ok(Err(err)) => (
             "failed".to_string(),
             None,
+            None,
             format!("event=failed command=\"{command_line}\" message=wait-error:{err}"),
         ),
         Err(_) => {
             let _ = child.start_kill();
-            let _ = child.wait().await;
-            (
-                "timeout".to_string(),
-                None,
-                format!("event=timeout command=\"{command_line}\""),
-            )
+            let signal = child
+                .wait()
+                .await
+                .ok()
+                .and_then(|exit| exit.signal())
+                .map(signal_name);
+            let message = match &signal {
+                Some(signal) => format!("event=timeout command=\"{command_line}\" signal={signal}"),
+                None => format!("event=timeout command=\"{command_line}\""),
+            };
+            ("timeout".to_string(), None, signal, message)
         }
     };
 
     let ended_at = Local::now();
-    logging::log_job(&paths.logs_dir, if status == "success" { "INFO" } else { "ERROR" }, &job.id, &run_id, &message)?;
+    if status != "success" {
+        logging::log_job(&paths.logs_dir, "ERROR", &job.id, &run_id, &message)?;
+    } else if job.log_successes {
+        logging::log_job(&paths.logs_dir, "INFO", &job.id, &run_id, &message)?;
+    }
+    // Background processes left behind by the job may keep stdout open; don't wait on them.
+    if let Some(reader) = tail_reader.as_mut() {
+        let _ = tokio::time::timeout(Duration::from_secs(1), &mut *reader).await;
+        reader.abort();
+    }
+    let output_tail = output_tail.lock().map(|tail| tail.iter().cloned().collect()).unwrap_or_default();
 
     Ok(ExecutionRecord {
         run_id,
@@ -301,22 +1317,280 @@ async fn execute_job(paths: AppPaths, job: JobConfig, trigger: &str) -> Result<E
         ended_at,
         status,
         exit_code,
+        signal,
+        hostname,
+        user,
         message,
+        output_tail,
     })
 }
 
-fn build_command(job: &JobConfig) -> (Command, String) {
-    let shell_mode = job.command.args.is_empty() && looks_like_shell(&job.command.program);
+fn spawn_output_tail(
+    stdout: ChildStdout,
+    max_lines: usize,
+    echo: bool,
+    tail: Arc<Mutex<VecDeque<String>>>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut reader = tokio::io::BufReader::new(stdout);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            if echo {
+                let _ = std::io::stdout().write_all(&buf);
+            }
+            let line = String::from_utf8_lossy(&buf);
+            let line: String = line.trim_end().chars().take(MAX_OUTPUT_LINE_CHARS).collect();
+            if let Ok(mut tail) = tail.lock() {
+                if tail.len() == max_lines {
+                    tail.pop_front();
+                }
+                tail.push_back(line);
+            }
+        }
+    })
+}
+
+async fn sleep_secs(seconds: u64) {
+    if seconds == 0 {
+        std::future::pending::<()>().await;
+    } else {
+        tokio::time::sleep(Duration::from_secs(seconds)).await;
+    }
+}
+
+fn spawn_hook(paths: AppPaths, job: JobConfig, record: ExecutionRecord) {
+    tokio::spawn(async move {
+        if let Err(err) = run_hook(&paths, &job, &record).await {
+            let _ = logging::log_daemon(&paths.logs_dir, "ERROR", &format!("run_hook failed: {err:#}"));
+        }
+    });
+}
+
+// Uses the system curl so the daemon needs no HTTP/TLS stack. The URL is not logged: ping
+// URLs usually embed the check's secret token.
+fn spawn_ping(paths: AppPaths, job_id: String, run_id: String, event: &'static str, url: String) {
+    tokio::spawn(async move {
+        let output = Command::new("curl")
+            .args(["-fsS", "-o", "/dev/null", "--max-time", &PING_TIMEOUT_SECONDS.to_string()])
+            .arg(&url)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await;
+        let (level, detail) = match output {
+            Ok(out) if out.status.success() => ("INFO", "status=success".to_string()),
+            Ok(out) => {
+                let stderr = String::from_utf8_lossy(&out.stderr);
+                (
+                    "WARN",
+                    format!(
+                        "status=failed exit_code={} message=\"{}\"",
+                        out.status.code().unwrap_or(-1),
+                        stderr.lines().next().unwrap_or("").trim()
+                    ),
+                )
+            }
+            Err(err) => ("WARN", format!("status=failed stage=spawn error=\"curl: {err}\"")),
+        };
+        let _ = logging::log_job(&paths.logs_dir, level, &job_id, &run_id, &format!("event=ping ping={event} {detail}"));
+    });
+}
+
+async fn run_hook(paths: &AppPaths, job: &JobConfig, record: &ExecutionRecord) -> Result<()> {
+    let (hook_name, hook) = match record.status.as_str() {
+        "success" => ("on_success", job.on_success.as_ref()),
+        "slow" | "slow-anomaly" => ("on_slow", job.on_slow.as_ref()),
+        _ => ("on_failure", job.on_failure.as_ref()),
+    };
+    let Some(hook) = hook else {
+        return Ok(());
+    };
+
+    let expanded = prepare_command(&paths.base_dir, hook, record.started_at);
+    let (mut command, command_line) = build_command(expanded.as_ref().unwrap_or(hook));
+    command.stdin(Stdio::null());
+    command.stdout(Stdio::null());
+    command.stderr(Stdio::null());
+    command.env("MACROND_JOB_ID", &job.id);
+    command.env("MACROND_RUN_ID", &record.run_id);
+    command.env("MACROND_STATUS", &record.status);
+    command.env(
+        "MACROND_EXIT_CODE",
+        record.exit_code.map(|c| c.to_string()).unwrap_or_default(),
+    );
+
+    let timeout = Duration::from_secs(HOOK_TIMEOUT_SECONDS);
+    let spawned = expanded
+        .and_then(|hook| configure_process(&mut command, &hook, &paths.base_dir))
+        .and_then(|()| Ok(command.spawn()?));
+    let (status, detail) = match spawned {
+        Ok(mut child) => match tokio::time::timeout(timeout, child.wait()).await {
+            Ok(Ok(exit)) if exit.success() => ("success", format!("exit_code={}", exit.code().unwrap_or(0))),
+            Ok(Ok(exit)) => ("failed", format!("exit_code={}", exit.code().unwrap_or(-1))),
+            Ok(Err(err)) => ("failed", format!("message=wait-error:{err}")),
+            Err(_) => {
+                let _ = child.start_kill();
+                let _ = child.wait().await;
+                ("timeout", format!("timeout_seconds={HOOK_TIMEOUT_SECONDS}"))
+            }
+        },
+        Err(err) => ("failed", format!("stage=spawn error={err:#}")),
+    };
+
+    logging::log_job(
+        &paths.logs_dir,
+        if status == "success" { "INFO" } else { "ERROR" },
+        &job.id,
+        &record.run_id,
+        &format!("event=hook hook={hook_name} command=\"{command_line}\" status={status} {detail}"),
+    )?;
+    Ok(())
+}
+
+pub fn resolve_command(base_dir: &Path, config: &CommandConfig) -> Result<CommandConfig> {
+    let mut resolved = config.clone();
+    if let Some(working_dir) = &config.working_dir {
+        let dir = config::resolve_working_dir(base_dir, working_dir);
+        resolved.working_dir = Some(dir.to_string_lossy().into_owned());
+    }
+    if let Some(env_file) = &config.env_file {
+        let path = config::resolve_working_dir(base_dir, env_file);
+        let mut env: HashMap<String, String> = load_env_file(&path)?.into_iter().collect();
+        env.extend(config.env.clone());
+        resolved.env = env;
+    }
+    Ok(resolved)
+}
+
+fn configure_process(command: &mut Command, config: &CommandConfig, base_dir: &Path) -> Result<()> {
+    config::check_working_dir(base_dir, config)?;
+    let resolved = resolve_command(base_dir, config)?;
+    if let Some(working_dir) = &resolved.working_dir {
+        command.current_dir(working_dir);
+    }
+    command.envs(&resolved.env);
+    if let Some(nice) = config.nice {
+        // SAFETY: setpriority is async-signal-safe and only touches the forked child.
+        unsafe {
+            command.pre_exec(move || {
+                if libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    if config.run_as_user.is_some() || config.run_as_group.is_some() {
+        configure_identity(command, config)?;
+    }
+    Ok(())
+}
+
+// Placeholders are expanded and args_file is read per run, so edits to the file apply to the next run.
+fn prepare_command(base_dir: &Path, config: &CommandConfig, at: chrono::DateTime<Local>) -> Result<CommandConfig> {
+    let mut expanded = config::expand_command(config, at)?;
+    if let Some(args_file) = &config.args_file {
+        let path = config::resolve_working_dir(base_dir, args_file);
+        let raw = std::fs::read_to_string(&path).with_context(|| format!("read args_file {}", path.display()))?;
+        for line in raw.lines() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            expanded.args.push(config::expand_placeholders(line, at)?);
+        }
+    }
+    Ok(expanded)
+}
+
+fn load_env_file(path: &Path) -> Result<Vec<(String, String)>> {
+    let raw = std::fs::read_to_string(path).with_context(|| format!("read env_file {}", path.display()))?;
+    let mut vars = Vec::new();
+    for (idx, line) in raw.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| anyhow!("invalid env_file line {} in {}", idx + 1, path.display()))?;
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)))
+            .unwrap_or(value);
+        vars.push((key.trim().to_string(), value.to_string()));
+    }
+    Ok(vars)
+}
+
+fn configure_identity(command: &mut Command, config: &CommandConfig) -> Result<()> {
+    if !nix::unistd::geteuid().is_root() {
+        return Err(anyhow!("run_as_user/run_as_group requires the daemon to run as root"));
+    }
+
+    let mut uid = None;
+    let mut gid = None;
+    if let Some(name) = &config.run_as_user {
+        let user = nix::unistd::User::from_name(name)?.ok_or_else(|| anyhow!("unknown user: {name}"))?;
+        uid = Some(user.uid.as_raw());
+        gid = Some(user.gid.as_raw());
+        for (key, value) in [
+            ("HOME", user.dir.to_string_lossy().into_owned()),
+            ("USER", user.name.clone()),
+            ("LOGNAME", user.name.clone()),
+        ] {
+            if !config.env.contains_key(key) {
+                command.env(key, value);
+            }
+        }
+    }
+    if let Some(name) = &config.run_as_group {
+        let group = nix::unistd::Group::from_name(name)?.ok_or_else(|| anyhow!("unknown group: {name}"))?;
+        gid = Some(group.gid.as_raw());
+    }
+
+    // SAFETY: only raw set*id syscalls run in the forked child; names were resolved above.
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(gid) = gid
+                && (libc::setgroups(1, &gid) != 0 || libc::setgid(gid) != 0)
+            {
+                return Err(std::io::Error::last_os_error());
+            }
+            if let Some(uid) = uid
+                && libc::setuid(uid) != 0
+            {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+fn build_command(config: &CommandConfig) -> (Command, String) {
+    let shell_mode = config.args.is_empty() && looks_like_shell(&config.program);
     if shell_mode {
-        let script = job.command.program.clone();
+        let script = config.program.clone();
         let mut command = Command::new("/bin/bash");
         command.arg("-lc").arg(&script);
+        apply_env_inheritance(&mut command, config);
         (command, format!("/bin/bash -lc {}", shell_escape(&script)))
     } else {
-        let mut command = Command::new(&job.command.program);
-        command.args(&job.command.args);
-        let mut full = job.command.program.clone();
-        for arg in &job.command.args {
+        let mut command = Command::new(&config.program);
+        command.args(&config.args);
+        if let Some(arg0) = &config.arg0 {
+            command.arg0(arg0);
+        }
+        apply_env_inheritance(&mut command, config);
+        let mut full = config.program.clone();
+        for arg in &config.args {
             full.push(' ');
             full.push_str(&shell_escape(arg));
         }
@@ -324,6 +1598,37 @@ fn build_command(job: &JobConfig) -> (Command, String) {
     }
 }
 
+// Runs before any variable is set on the command, so only the inherited daemon environment is affected.
+fn apply_env_inheritance(command: &mut Command, config: &CommandConfig) {
+    if config.env_clear {
+        command.env_clear();
+    }
+    for key in &config.env_remove {
+        command.env_remove(key);
+    }
+}
+
+fn current_hostname() -> Option<String> {
+    nix::unistd::gethostname()
+        .ok()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+fn current_user() -> Option<String> {
+    nix::unistd::User::from_uid(nix::unistd::getuid())
+        .ok()
+        .flatten()
+        .map(|user| user.name)
+        .or_else(|| std::env::var("USER").ok())
+}
+
+fn signal_name(signal: i32) -> String {
+    match nix::sys::signal::Signal::try_from(signal) {
+        Ok(signal) => signal.as_str().to_string(),
+        Err(_) => format!("SIG{signal}"),
+    }
+}
+
 fn looks_like_shell(program: &str) -> bool {
     [' ', '|', '>', '<', ';', '&', '`', '$']
         .iter()
@@ -338,45 +1643,49 @@ fn shell_escape(s: &str) -> String {
     }
 }
 
-fn write_state(
-    paths: &AppPaths,
-    pid: u32,
-    jobs: &[JobConfig],
-    next_runs: &HashMap<String, Option<chrono::DateTime<Local>>>,
-    last_result: &HashMap<String, ExecutionRecord>,
-    recent_runs: &[ExecutionRecord],
-    last_reload_error: Option<String>,
-) -> Result<()> {
+fn write_state(paths: &AppPaths, pid: u32, running: bool, rt: &Runtime) -> Result<()> {
     let mut views = Vec::new();
-    for job in jobs {
+    for job in &rt.jobs {
         views.push(JobView {
             id: job.id.clone(),
             name: job.name.clone(),
             enabled: job.enabled,
             schedule: scheduler::schedule_label(job),
-            next_run: next_runs.get(&job.id).cloned().flatten(),
-            last_result: last_result.get(&job.id).cloned(),
+            next_run: rt.next_runs.get(&job.id).cloned().flatten(),
+            last_result: rt.last_result.get(&job.id).cloned(),
+            duration: rt.duration_stats.get(&job.id).cloned(),
+            running: rt.running.get(&job.id).copied().unwrap_or(0),
         });
     }
 
     let state = DaemonState {
         updated_at: Local::now(),
         pid,
-        running: true,
-        last_reload_error,
+        running,
+        paused: rt.paused,
+        block_manual: rt.block_manual,
+        last_reload_error: rt.last_reload_error.clone(),
+        state_interval_seconds: rt.state_interval_seconds,
+        tick_ms: rt.tick_ms,
+        daemon_started_at: rt.daemon_started_at,
         jobs: views,
-        recent_runs: recent_runs.to_vec(),
+        recent_runs: rt.recent_runs.clone(),
     };
 
     let content = serde_json::to_string_pretty(&state)?;
-    std::fs::write(&paths.state_file, content)?;
+    paths::write_file(&paths.state_file, content)?;
     Ok(())
 }
 
+pub fn read_state(paths: &AppPaths) -> Result<DaemonState> {
+    let raw = std::fs::read_to_string(&paths.state_file)?;
+    let state = serde_json::from_str(&raw).context("parse state file")?;
+    Ok(state)
+}
+
 fn write_pid(path: &Path) -> Result<()> {
     let pid = std::process::id();
-    let mut file = OpenOptions::new().create(true).truncate(true).write(true).open(path)?;
-    file.write_all(pid.to_string().as_bytes())?;
+    paths::write_file(path, pid.to_string())?;
     Ok(())
 }
 
@@ -389,7 +1698,7 @@ fn read_pid(path: &Path) -> Result<Option<i32>> {
     Ok(pid)
 }
 
-fn is_pid_running(pid: i32) -> bool {
+pub fn is_pid_running(pid: i32) -> bool {
     nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid), None).is_ok()
 }
 
@@ -403,22 +1712,127 @@ impl Drop for PidGuard {
     }
 }
 
+/// Held by the daemon for its whole life. A pid in `daemon.pid` only counts while this lock
+/// is held, so a stale pid reused by another process (or by the daemon of another base-dir)
+/// is never signaled.
+fn lock_daemon(paths: &AppPaths) -> Result<Option<Flock<std::fs::File>>> {
+    let path = &paths.daemon_lock_file;
+    let file = paths::open_file(path, OpenOptions::new().create(true).truncate(false).write(true))
+        .with_context(|| format!("open lock file {}", path.display()))?;
+    match Flock::lock(file, FlockArg::LockExclusiveNonblock) {
+        Ok(lock) => Ok(Some(lock)),
+        Err((_, Errno::EWOULDBLOCK)) => Ok(None),
+        Err((_, errno)) => Err(anyhow!("lock {}: {errno}", path.display())),
+    }
+}
+
 pub fn daemon_running(paths: &AppPaths) -> Result<Option<i32>> {
     let Some(pid) = read_pid(&paths.pid_file)? else {
         return Ok(None);
     };
-
-    if is_pid_running(pid) {
-        Ok(Some(pid))
-    } else {
-        Ok(None)
+    if !is_pid_running(pid) {
+        return Ok(None);
+    }
+    // Daemons from before the lock file existed only have the pid to go by.
+    if paths.daemon_lock_file.exists() && lock_daemon(paths)?.is_some() {
+        return Ok(None);
     }
+    Ok(Some(pid))
+}
+
+// Written under a dot-prefixed temp name and renamed into place, so collect_requests never
+// sees a half-written `.json` file.
+fn write_request(paths: &AppPaths, name: &str, payload: &serde_json::Value) -> Result<()> {
+    let tmp = paths.requests_dir.join(format!(".{name}.{}.tmp", std::process::id()));
+    paths::write_file(&tmp, serde_json::to_vec(payload)?)?;
+    std::fs::rename(&tmp, paths.requests_dir.join(format!("{name}.json")))?;
+    Ok(())
 }
 
 pub fn submit_run_request(paths: &AppPaths, job_id: &str) -> Result<()> {
-    let req_id = Uuid::new_v4().to_string();
-    let path = paths.requests_dir.join(format!("{req_id}.json"));
     let payload = serde_json::json!({ "job_id": job_id });
-    std::fs::write(path, serde_json::to_vec(&payload)?)?;
-    Ok(())
+    write_request(paths, &Uuid::new_v4().to_string(), &payload)
+}
+
+/// Like `submit_run_request`, but the daemon reports the outcome; returns the id to pass to
+/// `wait_run_result`.
+pub fn submit_waiting_run_request(paths: &AppPaths, job_id: &str) -> Result<String> {
+    let wait_id = Uuid::new_v4().to_string();
+    let payload = serde_json::json!({ "job_id": job_id, "wait": true });
+    write_request(paths, &wait_id, &payload)?;
+    Ok(wait_id)
+}
+
+/// Collects the result of a waiting run request if the daemon has written it.
+pub fn take_run_result(paths: &AppPaths, wait_id: &str) -> Result<Option<ExecutionRecord>> {
+    let path = paths.results_dir.join(format!("{wait_id}.json"));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = std::fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))?;
+    let _ = std::fs::remove_file(&path);
+    serde_json::from_str(&raw).map(Some).context("parse run result")
+}
+
+pub async fn wait_run_result(paths: &AppPaths, wait_id: &str) -> Result<ExecutionRecord> {
+    let path = paths.results_dir.join(format!("{wait_id}.json"));
+    loop {
+        // Checked before the daemon so a result written just before it exits is not lost.
+        if let Some(record) = take_run_result(paths, wait_id)? {
+            return Ok(record);
+        }
+        if daemon_running(paths)?.is_none() && !path.exists() {
+            return Err(anyhow::Error::new(MacrondError::DaemonNotRunning)
+                .context("daemon stopped before the queued run finished"));
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+fn write_run_results(paths: &AppPaths, waiting: &[String], record: &ExecutionRecord) {
+    for wait_id in waiting {
+        let path = paths.results_dir.join(format!("{wait_id}.json"));
+        let tmp = paths.results_dir.join(format!(".{wait_id}.tmp"));
+        let written = serde_json::to_vec_pretty(record)
+            .map_err(anyhow::Error::from)
+            .and_then(|raw| Ok(paths::write_file(&tmp, raw)?))
+            .and_then(|()| Ok(std::fs::rename(&tmp, &path)?));
+        if let Err(err) = written {
+            let _ = logging::log_daemon(&paths.logs_dir, "ERROR", &format!("write run result {}: {err:#}", path.display()));
+        }
+    }
+}
+
+fn write_skipped_results(paths: &AppPaths, waiting: &[String], job_id: &str, reason: &str) {
+    if waiting.is_empty() {
+        return;
+    }
+    let now = Local::now();
+    let record = ExecutionRecord {
+        run_id: String::new(),
+        job_id: job_id.to_string(),
+        trigger: "manual".to_string(),
+        started_at: now,
+        ended_at: now,
+        status: "skipped".to_string(),
+        exit_code: None,
+        signal: None,
+        hostname: current_hostname(),
+        user: current_user(),
+        message: reason.to_string(),
+        output_tail: Vec::new(),
+    };
+    write_run_results(paths, waiting, &record);
+}
+
+pub fn submit_pause_request(paths: &AppPaths, block_manual: bool) -> Result<()> {
+    let _ = std::fs::remove_file(paths.requests_dir.join("resume.json"));
+    let payload = serde_json::json!({ "action": "pause", "block_manual": block_manual });
+    write_request(paths, "pause", &payload)
+}
+
+pub fn submit_resume_request(paths: &AppPaths) -> Result<()> {
+    let _ = std::fs::remove_file(paths.requests_dir.join("pause.json"));
+    let payload = serde_json::json!({ "action": "resume" });
+    write_request(paths, "resume", &payload)
 }
@@ -0,0 +1,56 @@
+use crate::config::IdCollision;
+use std::path::PathBuf;
+
+/// Failure modes callers may want to tell apart; everything else stays `anyhow`.
+#[derive(Debug, thiserror::Error)]
+pub enum MacrondError {
+    #[error("job not found: {0}")]
+    JobNotFound(String),
+    #[error("'{pattern}' matches {} jobs, pick one:\n  {}", candidates.len(), candidates.join("\n  "))]
+    AmbiguousJob { pattern: String, candidates: Vec<String> },
+    #[error("invalid config {}: {message}", path.display())]
+    InvalidConfig { path: PathBuf, message: String },
+    #[error("{0}")]
+    DuplicateId(IdCollision),
+    #[error("invalid schedule for job {job_id}: {message}")]
+    InvalidSchedule { job_id: String, message: String },
+    #[error("daemon is not running")]
+    DaemonNotRunning,
+    #[error("daemon is already running with pid {0}")]
+    DaemonAlreadyRunning(i32),
+    #[error("job {job_id} is already running and allow_overlap is false (lock {})", lock.display())]
+    JobRunning { job_id: String, lock: PathBuf },
+    #[error("job {job_id} was not run: {reason}")]
+    RunSkipped { job_id: String, reason: String },
+    #[error("job {job_id} finished with status={status}")]
+    RunFailed { job_id: String, status: String },
+    #[error("io error on {}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl MacrondError {
+    /// Process exit code for the CLI; 1 is left for unclassified errors, 2 is clap's usage error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            MacrondError::JobNotFound(_) | MacrondError::AmbiguousJob { .. } => 3,
+            MacrondError::InvalidConfig { .. }
+            | MacrondError::DuplicateId(_)
+            | MacrondError::InvalidSchedule { .. } => 4,
+            MacrondError::DaemonNotRunning
+            | MacrondError::DaemonAlreadyRunning(_)
+            | MacrondError::JobRunning { .. }
+            | MacrondError::RunSkipped { .. } => 5,
+            MacrondError::Io { .. } => 6,
+            MacrondError::RunFailed { .. } => 7,
+        }
+    }
+
+    /// Finds the first classified error in an `anyhow` chain.
+    pub fn find(err: &anyhow::Error) -> Option<&MacrondError> {
+        err.chain()
+            .find_map(|cause| cause.downcast_ref::<MacrondError>())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::config;
+use crate::daemon;
+use crate::logging;
+use crate::paths::{self, AppPaths};
+use anyhow::{Context, Result, anyhow, bail};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub fn install_agent(paths: &AppPaths, name: Option<&str>) -> Result<()> {
+    let label = agent_label(paths, name);
+    let plist_path = plist_path(&label)?;
+    check_agent_owner(paths, &label, &plist_path)?;
+    let exe = std::env::current_exe().context("resolve current exe")?;
+
+    let mut daemon_args = vec![
+        "--log-level".to_string(),
+        logging::level().as_str().to_string(),
+        "--history-size".to_string(),
+        daemon::history_size().to_string(),
+        "--file-mode".to_string(),
+        format!("{:04o}", paths::file_mode()),
+    ];
+    if config::verify_checksums() {
+        daemon_args.push("--verify-jobs".to_string());
+    }
+    daemon_args.extend(paths.config_args().iter().map(|arg| arg.to_string_lossy().into_owned()));
+    let content = render_plist(
+        &label,
+        &exe.to_string_lossy(),
+        &paths.base_dir.to_string_lossy(),
+        &paths.logs_dir.join("launchd.log").to_string_lossy(),
+        &daemon_args,
+    );
+    if let Some(parent) = plist_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&plist_path, content)
+        .with_context(|| format!("write {}", plist_path.display()))?;
+    println!("agent plist written: {}", plist_path.display());
+
+    match Command::new("launchctl").arg("load").arg("-w").arg(&plist_path).status() {
+        Ok(status) if status.success() => println!("agent loaded: {label}"),
+        Ok(status) => println!("launchctl load failed ({status}), load it manually"),
+        Err(err) => println!("launchctl unavailable ({err}), load it manually"),
+    }
+    Ok(())
+}
+
+pub fn uninstall_agent(paths: &AppPaths, name: Option<&str>) -> Result<()> {
+    let label = agent_label(paths, name);
+    let plist_path = plist_path(&label)?;
+    if !plist_path.exists() {
+        println!("agent not installed: {}", plist_path.display());
+        return Ok(());
+    }
+    check_agent_owner(paths, &label, &plist_path)?;
+
+    match Command::new("launchctl").arg("unload").arg("-w").arg(&plist_path).status() {
+        Ok(status) if status.success() => println!("agent unloaded: {label}"),
+        Ok(status) => println!("launchctl unload failed ({status})"),
+        Err(err) => println!("launchctl unavailable ({err})"),
+    }
+    std::fs::remove_file(&plist_path)
+        .with_context(|| format!("remove {}", plist_path.display()))?;
+    println!("agent plist removed: {}", plist_path.display());
+    Ok(())
+}
+
+fn agent_label(paths: &AppPaths, name: Option<&str>) -> String {
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => paths
+            .base_dir
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("default")
+            .to_string(),
+    };
+    let name: String = name
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() || "-_.".contains(ch) { ch } else { '-' })
+        .collect();
+    format!("com.macrond.{name}")
+}
+
+// The default label comes from the base-dir's folder name, so two base-dirs such as
+// ~/a/macrond and ~/b/macrond would otherwise replace each other's agent.
+fn check_agent_owner(paths: &AppPaths, label: &str, plist_path: &Path) -> Result<()> {
+    let Ok(existing) = std::fs::read_to_string(plist_path) else {
+        return Ok(());
+    };
+    let base_dir = format!("<string>{}</string>", xml_escape(&paths.base_dir.to_string_lossy()));
+    if !existing.contains(&base_dir) {
+        bail!(
+            "agent {label} belongs to another base-dir (see {}), pass --name to use a different label",
+            plist_path.display()
+        );
+    }
+    Ok(())
+}
+
+fn plist_path(label: &str) -> Result<PathBuf> {
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?;
+    Ok(PathBuf::from(home)
+        .join("Library/LaunchAgents")
+        .join(format!("{label}.plist")))
+}
+
+fn render_plist(
+    label: &str,
+    exe: &str,
+    base_dir: &str,
+    log_file: &str,
+    daemon_args: &[String],
+) -> String {
+    let daemon_args: String = daemon_args
+        .iter()
+        .map(|arg| format!("\n        <string>{}</string>", xml_escape(arg)))
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{label}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{exe}</string>
+        <string>--base-dir</string>
+        <string>{base_dir}</string>
+        <string>daemon</string>{daemon_args}
+    </array>
+    <key>WorkingDirectory</key>
+    <string>{base_dir}</string>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <true/>
+    <key>StandardOutPath</key>
+    <string>{log_file}</string>
+    <key>StandardErrorPath</key>
+    <string>{log_file}</string>
+</dict>
+</plist>
+"#,
+        label = xml_escape(label),
+        exe = xml_escape(exe),
+        base_dir = xml_escape(base_dir),
+        log_file = xml_escape(log_file),
+    )
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,12 @@
+pub mod app;
+pub mod cli;
+mod config;
+mod daemon;
+pub mod error;
+mod launchd;
+mod logging;
+pub mod model;
+mod paths;
+pub mod scheduler;
+mod top;
+mod tui;
@@ -1,8 +1,61 @@
+use crate::paths;
 use anyhow::Result;
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fs::{OpenOptions, read_dir, remove_file};
 use std::io::Write;
 use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%:z";
+const MAX_MESSAGE_CHARS: usize = 8192;
+const NEWLINE_MARKER: &str = "\\n";
+
+static LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "DEBUG" => Some(LogLevel::Debug),
+            "INFO" => Some(LogLevel::Info),
+            "WARN" => Some(LogLevel::Warn),
+            "ERROR" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+pub fn set_level(level: LogLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> LogLevel {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => LogLevel::Debug,
+        1 => LogLevel::Info,
+        2 => LogLevel::Warn,
+        _ => LogLevel::Error,
+    }
+}
 
 pub fn log_daemon(logs_dir: &Path, level: &str, message: &str) -> Result<()> {
     write_line(logs_dir, "daemon", level, None, None, message)
@@ -26,12 +79,15 @@ fn write_line(
     run_id: Option<&str>,
     message: &str,
 ) -> Result<()> {
+    if LogLevel::from_label(level).is_some_and(|l| l < self::level()) {
+        return Ok(());
+    }
     let now = Local::now();
     let filename = format!("{}-{:04}-{:02}-{:02}.log", prefix, now.year(), now.month(), now.day());
     let path = logs_dir.join(filename);
-    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut file = paths::open_file(&path, OpenOptions::new().create(true).append(true))?;
 
-    let mut line = format!("{} {}", now.format("%Y-%m-%d %H:%M:%S%:z"), level);
+    let mut line = format!("{} {}", now.format(TIMESTAMP_FORMAT), level);
     if let Some(id) = job_id {
         line.push_str(&format!(" job_id={id}"));
     }
@@ -39,13 +95,29 @@ fn write_line(
         line.push_str(&format!(" run_id={id}"));
     }
     line.push(' ');
-    line.push_str(message);
+    line.push_str(&sanitize_message(message));
     line.push('\n');
 
     file.write_all(line.as_bytes())?;
     Ok(())
 }
 
+// Readers parse the log one physical line per record, so embedded newlines become a visible
+// marker and oversized messages are cut.
+fn sanitize_message(message: &str) -> Cow<'_, str> {
+    let mut flat = Cow::Borrowed(message);
+    if message.contains(['\n', '\r']) {
+        flat = Cow::Owned(message.replace("\r\n", NEWLINE_MARKER).replace(['\n', '\r'], NEWLINE_MARKER));
+    }
+    let chars = flat.chars().count();
+    if chars <= MAX_MESSAGE_CHARS {
+        return flat;
+    }
+    let mut cut: String = flat.chars().take(MAX_MESSAGE_CHARS).collect();
+    cut.push_str(&format!(" ...[truncated {} chars]", chars - MAX_MESSAGE_CHARS));
+    Cow::Owned(cut)
+}
+
 pub fn cleanup_old_logs(logs_dir: &Path, keep_days: i64) -> Result<()> {
     let today = Local::now().date_naive();
     for entry in read_dir(logs_dir)? {
@@ -59,15 +131,7 @@ pub fn cleanup_old_logs(logs_dir: &Path, keep_days: i64) -> Result<()> {
             continue;
         };
 
-        let Some(date_str) = file_name
-            .strip_prefix("daemon-")
-            .or_else(|| file_name.strip_prefix("job-"))
-            .and_then(|s| s.strip_suffix(".log"))
-        else {
-            continue;
-        };
-
-        let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") else {
+        let Some(date) = log_file_date(file_name) else {
             continue;
         };
 
@@ -78,3 +142,92 @@ pub fn cleanup_old_logs(logs_dir: &Path, keep_days: i64) -> Result<()> {
 
     Ok(())
 }
+
+pub fn log_file_date(file_name: &str) -> Option<NaiveDate> {
+    let date_str = file_name
+        .strip_prefix("daemon-")
+        .or_else(|| file_name.strip_prefix("job-"))
+        .and_then(|s| s.strip_suffix(".log"))?;
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok()
+}
+
+/// A log line split into its fixed prefix and the `key=value` fields of the message.
+#[derive(Debug, Serialize)]
+pub struct LogLine {
+    pub ts: DateTime<FixedOffset>,
+    pub level: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+pub fn parse_line(line: &str) -> Option<LogLine> {
+    let ts = line_timestamp(line)?;
+    let mut parts = line.splitn(4, ' ');
+    let level = parts.nth(2)?.to_string();
+    let mut rest = parts.next().unwrap_or("");
+    let mut take = |key: &str| {
+        let value = rest.strip_prefix(key)?;
+        let (value, tail) = value.split_once(' ').unwrap_or((value, ""));
+        rest = tail;
+        Some(value.to_string())
+    };
+    let job_id = take("job_id=");
+    let run_id = take("run_id=");
+    Some(LogLine {
+        ts,
+        level,
+        job_id,
+        run_id,
+        fields: parse_fields(rest),
+        message: rest.to_string(),
+    })
+}
+
+// Quoted values end at a `"` followed by a space or the end of the line, so
+// commands with inner quotes (command="sh -c 'echo "$1"'") stay in one field.
+fn parse_fields(message: &str) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    let mut rest = message.trim_start();
+    while !rest.is_empty() {
+        let word_end = rest.find(' ').unwrap_or(rest.len());
+        let Some((key, _)) = rest[..word_end].split_once('=') else {
+            rest = rest[word_end..].trim_start();
+            continue;
+        };
+        let value_start = key.len() + 1;
+        let (value, end) = match rest[value_start..].strip_prefix('"') {
+            Some(quoted) => {
+                let close = quoted
+                    .match_indices('"')
+                    .map(|(i, _)| i)
+                    .find(|&i| quoted[i + 1..].is_empty() || quoted[i + 1..].starts_with(' '));
+                match close {
+                    Some(i) => (&quoted[..i], value_start + i + 2),
+                    None => (quoted, rest.len()),
+                }
+            }
+            None => (&rest[value_start..word_end], word_end),
+        };
+        if !key.is_empty() {
+            fields.insert(key.to_string(), value.to_string());
+        }
+        rest = rest[end..].trim_start();
+    }
+    fields
+}
+
+pub fn line_level(line: &str) -> Option<&str> {
+    line.split(' ').nth(2)
+}
+
+pub fn line_timestamp(line: &str) -> Option<DateTime<FixedOffset>> {
+    let mut parts = line.splitn(3, ' ');
+    let date = parts.next()?;
+    let time = parts.next()?;
+    DateTime::parse_from_str(&format!("{date} {time}"), TIMESTAMP_FORMAT).ok()
+}
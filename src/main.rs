@@ -1,19 +1,11 @@
-mod app;
-mod cli;
-mod config;
-mod daemon;
-mod logging;
-mod model;
-mod paths;
-mod scheduler;
-mod tui;
-
 use clap::Parser;
+use macrond::error::MacrondError;
+use macrond::{app, cli};
 
 #[tokio::main]
 async fn main() {
     if let Err(err) = app::run(cli::Cli::parse()).await {
         eprintln!("error: {err:#}");
-        std::process::exit(1);
+        std::process::exit(MacrondError::find(&err).map_or(1, MacrondError::exit_code));
     }
 }
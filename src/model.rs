@@ -1,31 +1,109 @@
 use chrono::{DateTime, Local};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobConfig {
     pub id: String,
     pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub group: Option<String>,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    #[serde(default)]
+    pub manual_only: bool,
+    #[serde(default = "default_allow_overlap")]
+    pub allow_overlap: bool,
     pub schedule: ScheduleConfig,
     pub command: CommandConfig,
-    #[serde(default = "default_timeout")]
+    #[serde(default = "default_timeout", deserialize_with = "deserialize_timeout")]
     pub timeout_seconds: u64,
+    #[serde(default)]
+    pub warn_after_seconds: Option<u64>,
+    #[serde(default)]
+    pub slow_anomaly_factor: Option<f64>,
+    #[serde(default)]
+    pub max_manual_runs: Option<u32>,
+    #[serde(default)]
+    pub disable_after_failures: Option<u32>,
+    /// Scheduled runs only fire inside this window (`YYYY-MM-DD HH:MM` or RFC3339, both inclusive).
+    #[serde(default)]
+    pub active_from: Option<String>,
+    #[serde(default)]
+    pub active_until: Option<String>,
+    #[serde(default)]
+    pub output_tail_lines: Option<usize>,
+    /// When false, successful runs write no start/success lines; failures and timeouts still do.
+    #[serde(default = "default_log_successes")]
+    pub log_successes: bool,
+    /// One-shot jobs only: remove the job file after the run succeeds instead of disabling it.
+    #[serde(default)]
+    pub delete_after_run: bool,
+    #[serde(default)]
+    pub on_success: Option<CommandConfig>,
+    #[serde(default)]
+    pub on_failure: Option<CommandConfig>,
+    #[serde(default)]
+    pub on_slow: Option<CommandConfig>,
+    /// GET after each successful daemon run, for an external dead man's switch.
+    #[serde(default)]
+    pub ping_url_on_success: Option<String>,
+    #[serde(default)]
+    pub ping_url_on_start: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Local>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Local>>,
+    /// Set when a one-shot job has fired successfully and was disabled.
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Local>>,
+    /// Fields macrond does not know about, kept so saving a hand-written file does not drop them.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum ScheduleConfig {
     Cron { expression: String },
+    /// Steady cadence anchored to the daemon start time instead of the wall clock.
+    Interval {
+        every_seconds: u64,
+        #[serde(default)]
+        delay_seconds: u64,
+    },
     Simple {
         repeat: Repeat,
         time: Option<String>,
+        #[serde(default)]
+        times: Vec<String>,
+        #[serde(default, deserialize_with = "deserialize_weekday")]
         weekday: Option<u8>,
+        #[serde(default, deserialize_with = "deserialize_day")]
         day: Option<u8>,
+        #[serde(default, deserialize_with = "deserialize_week")]
+        week: Option<i8>,
         once_at: Option<String>,
+        #[serde(default)]
+        minute: Option<u8>,
+        #[serde(default)]
+        month: Option<u8>,
+        #[serde(default)]
+        dst_gap: DstGap,
     },
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DstGap {
+    #[default]
+    Shift,
+    Skip,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Repeat {
@@ -34,16 +112,37 @@ pub enum Repeat {
     Monthly,
     EveryMinute,
     Once,
+    Hourly,
+    Yearly,
+    MonthlyWeekday,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CommandConfig {
     pub program: String,
     #[serde(default)]
     pub args: Vec<String>,
+    #[serde(default)]
+    pub args_file: Option<String>,
+    #[serde(default)]
+    pub arg0: Option<String>,
     pub working_dir: Option<String>,
     #[serde(default)]
     pub env: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    pub env_file: Option<String>,
+    #[serde(default)]
+    pub env_clear: bool,
+    #[serde(default)]
+    pub env_remove: Vec<String>,
+    #[serde(default)]
+    pub nice: Option<i32>,
+    #[serde(default)]
+    pub run_as_user: Option<String>,
+    #[serde(default)]
+    pub run_as_group: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,7 +154,15 @@ pub struct ExecutionRecord {
     pub ended_at: DateTime<Local>,
     pub status: String,
     pub exit_code: Option<i32>,
+    #[serde(default)]
+    pub signal: Option<String>,
+    #[serde(default)]
+    pub hostname: Option<String>,
+    #[serde(default)]
+    pub user: Option<String>,
     pub message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_tail: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +173,16 @@ pub struct JobView {
     pub schedule: String,
     pub next_run: Option<DateTime<Local>>,
     pub last_result: Option<ExecutionRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<DurationStats>,
+    #[serde(default)]
+    pub running: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DurationStats {
+    pub ema_seconds: f64,
+    pub samples: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,7 +190,17 @@ pub struct DaemonState {
     pub updated_at: DateTime<Local>,
     pub pid: u32,
     pub running: bool,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub block_manual: bool,
     pub last_reload_error: Option<String>,
+    #[serde(default)]
+    pub state_interval_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tick_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daemon_started_at: Option<DateTime<Local>>,
     pub jobs: Vec<JobView>,
     pub recent_runs: Vec<ExecutionRecord>,
 }
@@ -82,6 +209,81 @@ fn default_enabled() -> bool {
     true
 }
 
+fn default_allow_overlap() -> bool {
+    true
+}
+
+fn default_log_successes() -> bool {
+    true
+}
+
 fn default_timeout() -> u64 {
     3600
 }
+
+fn deserialize_timeout<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<u64>::deserialize(deserializer)?.unwrap_or(0))
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DayValue {
+    Number(u8),
+    Name(String),
+}
+
+fn deserialize_day<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<DayValue>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(DayValue::Number(n)) => Ok(Some(n)),
+        Some(DayValue::Name(name)) => crate::scheduler::parse_day(&name)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WeekValue {
+    Number(i8),
+    Name(String),
+}
+
+fn deserialize_week<'de, D>(deserializer: D) -> Result<Option<i8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<WeekValue>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(WeekValue::Number(n)) => Ok(Some(n)),
+        Some(WeekValue::Name(name)) => crate::scheduler::parse_week(&name)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WeekdayValue {
+    Number(u8),
+    Name(String),
+}
+
+fn deserialize_weekday<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<WeekdayValue>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(WeekdayValue::Number(n)) => Ok(Some(n)),
+        Some(WeekdayValue::Name(name)) => crate::scheduler::parse_weekday(&name)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
@@ -1,5 +1,14 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow, bail};
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions, Permissions};
+use std::io::Write;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+pub const DEFAULT_FILE_MODE: u32 = 0o600;
+
+static FILE_MODE: AtomicU32 = AtomicU32::new(DEFAULT_FILE_MODE);
 
 #[derive(Debug, Clone)]
 pub struct AppPaths {
@@ -8,8 +17,16 @@ pub struct AppPaths {
     pub logs_dir: PathBuf,
     pub run_dir: PathBuf,
     pub requests_dir: PathBuf,
+    pub results_dir: PathBuf,
     pub pid_file: PathBuf,
+    pub daemon_lock_file: PathBuf,
     pub state_file: PathBuf,
+    pub defaults_file: PathBuf,
+    // Set by `--config`: jobs come from this one file instead of `jobs/` and `defaults.json`.
+    pub config_file: Option<PathBuf>,
+    pub last_success_dir: PathBuf,
+    pub last_failure_dir: PathBuf,
+    pub locks_dir: PathBuf,
 }
 
 impl AppPaths {
@@ -19,24 +36,149 @@ impl AppPaths {
         let logs_dir = base_dir.join("logs");
         let run_dir = base_dir.join("run");
         let requests_dir = run_dir.join("requests");
+        let results_dir = run_dir.join("results");
         let pid_file = run_dir.join("daemon.pid");
+        let daemon_lock_file = run_dir.join("daemon.lock");
         let state_file = run_dir.join("state.json");
+        let defaults_file = base_dir.join("defaults.json");
+        let last_success_dir = run_dir.join("last-success");
+        let last_failure_dir = run_dir.join("last-failure");
+        let locks_dir = run_dir.join("locks");
         Ok(Self {
             base_dir,
             jobs_dir,
             logs_dir,
             run_dir,
             requests_dir,
+            results_dir,
             pid_file,
+            daemon_lock_file,
             state_file,
+            defaults_file,
+            config_file: None,
+            last_success_dir,
+            last_failure_dir,
+            locks_dir,
         })
     }
 
+    pub fn resolve(base_dir: Option<&Path>, profile: Option<&str>) -> Result<Self> {
+        let base_dir = match (base_dir, profile) {
+            (Some(dir), _) => dir.to_path_buf(),
+            (None, Some(name)) => profile_dir(name)?,
+            (None, None) => default_base_dir()?,
+        };
+        std::fs::create_dir_all(&base_dir)?;
+        Self::new(base_dir)
+    }
+
     pub fn ensure_dirs(&self) -> Result<()> {
         std::fs::create_dir_all(&self.jobs_dir)?;
         std::fs::create_dir_all(&self.logs_dir)?;
         std::fs::create_dir_all(&self.run_dir)?;
         std::fs::create_dir_all(&self.requests_dir)?;
+        std::fs::create_dir_all(&self.results_dir)?;
+        std::fs::create_dir_all(&self.last_success_dir)?;
+        std::fs::create_dir_all(&self.last_failure_dir)?;
+        std::fs::create_dir_all(&self.locks_dir)?;
         Ok(())
     }
+
+    pub fn with_config_file(mut self, file: Option<&Path>) -> Result<Self> {
+        if let Some(file) = file {
+            // Not canonicalized: a mounted config is often a symlink that is swapped on update,
+            // and the resolved target would disappear with the old version.
+            let file = std::path::absolute(file)?;
+            if !file.is_file() {
+                bail!("config file {} does not exist or is not a file", file.display());
+            }
+            self.config_file = Some(file);
+        }
+        Ok(self)
+    }
+
+    /// Arguments that make a child macrond process read jobs from the same place.
+    pub fn config_args(&self) -> Vec<OsString> {
+        match &self.config_file {
+            Some(file) => vec!["--config".into(), file.into()],
+            None => Vec::new(),
+        }
+    }
+
+    // A combined config file is treated as read-only, e.g. a mount in a container.
+    pub fn check_jobs_writable(&self) -> Result<()> {
+        if let Some(file) = &self.config_file {
+            bail!("jobs are loaded from {} (--config), edit that file instead", file.display());
+        }
+        Ok(())
+    }
+
+    pub fn check_writable(&self) -> Result<()> {
+        for dir in [&self.run_dir, &self.logs_dir] {
+            let probe = dir.join(format!(".write-test-{}", std::process::id()));
+            std::fs::write(&probe, b"")
+                .and_then(|()| std::fs::remove_file(&probe))
+                .map_err(|err| {
+                    anyhow!(
+                        "base_dir not writable: cannot write to {} ({err})",
+                        dir.display()
+                    )
+                })?;
+        }
+        Ok(())
+    }
+}
+
+pub fn set_file_mode(mode: u32) {
+    FILE_MODE.store(mode, Ordering::Relaxed);
+}
+
+pub fn file_mode() -> u32 {
+    FILE_MODE.load(Ordering::Relaxed)
+}
+
+pub fn parse_file_mode(value: &str) -> Result<u32> {
+    let digits = value.trim().trim_start_matches("0o");
+    let mode = u32::from_str_radix(digits, 8)
+        .ok()
+        .filter(|mode| *mode <= 0o777)
+        .ok_or_else(|| anyhow!("invalid file mode '{value}', expected octal like 0600"))?;
+    if mode & 0o600 != 0o600 {
+        bail!("file mode {mode:04o} must keep owner read/write");
+    }
+    Ok(mode)
+}
+
+/// Opens a file macrond owns (logs, state, run markers, job files) with the configured mode.
+/// Files created under an older mode are chmod'ed on the next write.
+pub fn open_file(path: &Path, options: &mut OpenOptions) -> std::io::Result<File> {
+    let mode = file_mode();
+    let file = options.mode(mode).open(path)?;
+    if file.metadata()?.permissions().mode() & 0o777 != mode {
+        // Not fatal: the file may belong to another user, e.g. a log under a shared base-dir.
+        let _ = file.set_permissions(Permissions::from_mode(mode));
+    }
+    Ok(file)
+}
+
+pub fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let mut file = open_file(path, OpenOptions::new().write(true).create(true).truncate(true))?;
+    file.write_all(contents.as_ref())
+}
+
+pub fn default_base_dir() -> Result<PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "macrond")
+        .ok_or_else(|| anyhow!("cannot determine home directory, pass --base-dir"))?;
+    Ok(dirs.data_dir().to_path_buf())
+}
+
+pub fn profiles_dir() -> Result<PathBuf> {
+    Ok(default_base_dir()?.join("profiles"))
+}
+
+fn profile_dir(name: &str) -> Result<PathBuf> {
+    if name.is_empty() || !name.chars().all(|ch| ch.is_ascii_alphanumeric() || "-_".contains(ch)) {
+        bail!("invalid profile name '{name}', use letters, digits, '-' or '_'");
+    }
+    Ok(profiles_dir()?.join(name))
 }
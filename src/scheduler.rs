@@ -1,148 +1,476 @@
-use crate::model::{JobConfig, Repeat, ScheduleConfig};
-use anyhow::{Result, anyhow};
+use crate::error::MacrondError;
+use crate::model::{DstGap, JobConfig, Repeat, ScheduleConfig};
+use anyhow::{Result, anyhow, bail};
 use chrono::{
-    DateTime, Datelike, Days, Local, LocalResult, NaiveDateTime, NaiveTime, TimeZone, Timelike,
-    Utc, Weekday,
+    DateTime, Datelike, Days, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone,
+    Timelike, Weekday,
 };
 use std::str::FromStr;
+use std::sync::atomic::{AtomicI64, Ordering};
 
-pub fn next_run_after(job: &JobConfig, after: DateTime<Local>) -> Result<Option<DateTime<Local>>> {
-    if !job.enabled {
+pub const LAST_DAY: u8 = 0;
+pub const LAST_WEEK: i8 = -1;
+const MAX_GAP_MINUTES: i64 = 24 * 60;
+
+static START_ANCHOR: AtomicI64 = AtomicI64::new(i64::MIN);
+
+/// Daemon start time that `interval` schedules count from.
+pub fn set_start_anchor(at: DateTime<Local>) {
+    START_ANCHOR.store(at.timestamp(), Ordering::Relaxed);
+}
+
+pub fn start_anchor() -> Option<DateTime<Local>> {
+    match START_ANCHOR.load(Ordering::Relaxed) {
+        i64::MIN => None,
+        ts => Local.timestamp_opt(ts, 0).single(),
+    }
+}
+
+pub fn next_run_after(job: &JobConfig, after: DateTime<Local>) -> Result<Option<DateTime<Local>>, MacrondError> {
+    if !job.enabled || job.manual_only {
         return Ok(None);
     }
 
+    let next = active_window(job).and_then(|(from, until)| {
+        let after = match from {
+            Some(from) if after < from => from - TimeDelta::seconds(1),
+            _ => after,
+        };
+        Ok(next_scheduled(job, after)?.filter(|next| until.is_none_or(|until| *next <= until)))
+    });
+    next.map_err(|err| MacrondError::InvalidSchedule {
+        job_id: job.id.clone(),
+        message: format!("{err:#}"),
+    })
+}
+
+type Bound = Option<DateTime<Local>>;
+
+/// Parsed `active_from` / `active_until` bounds of a job.
+pub fn active_window(job: &JobConfig) -> Result<(Bound, Bound)> {
+    let bound = |field: &str, value: Option<&str>| -> Result<Bound> {
+        value
+            .map(|v| {
+                parse_local_datetime(field, v, DstGap::Shift)?
+                    .ok_or_else(|| anyhow!("{field} '{v}' does not exist in local time"))
+            })
+            .transpose()
+    };
+    Ok((
+        bound("active_from", job.active_from.as_deref())?,
+        bound("active_until", job.active_until.as_deref())?,
+    ))
+}
+
+pub fn in_active_window(job: &JobConfig, at: DateTime<Local>) -> bool {
+    match active_window(job) {
+        Ok((from, until)) => from.is_none_or(|from| at >= from) && until.is_none_or(|until| at <= until),
+        Err(_) => false,
+    }
+}
+
+fn next_scheduled(job: &JobConfig, after: DateTime<Local>) -> Result<Option<DateTime<Local>>> {
     match &job.schedule {
         ScheduleConfig::Cron { expression } => {
             let schedule = cron::Schedule::from_str(expression)
                 .map_err(|e| anyhow!("invalid cron expression: {e}"))?;
-            let next = schedule.after(&after.with_timezone(&Utc)).next();
-            Ok(next.map(|dt| dt.with_timezone(&Local)))
+            Ok(schedule.after(&after).next())
+        }
+        ScheduleConfig::Interval {
+            every_seconds,
+            delay_seconds,
+        } => {
+            let anchor = start_anchor().ok_or_else(|| anyhow!("daemon start time is unknown"))?;
+            Ok(Some(next_interval(after, anchor, *every_seconds, *delay_seconds)?))
         }
         ScheduleConfig::Simple {
             repeat,
             time,
+            times,
             weekday,
             day,
+            week,
             once_at,
+            minute,
+            month,
+            dst_gap,
         } => {
-            Ok(Some(match repeat {
+            let gap = *dst_gap;
+            Ok(match repeat {
                 Repeat::Daily => {
-                    let t = parse_hhmm(time.as_deref())?;
-                    next_daily(after, t)
+                    let times = daily_times(time.as_deref(), times)?;
+                    next_daily(after, &times, gap)
                 }
                 Repeat::Weekly => {
                     let t = parse_hhmm(time.as_deref())?;
                     let weekday = weekday.ok_or_else(|| anyhow!("weekday is required"))?;
-                    next_weekly(after, t, weekday)
+                    next_weekly(after, t, weekday, gap)
                 }
                 Repeat::Monthly => {
                     let t = parse_hhmm(time.as_deref())?;
                     let day = day.ok_or_else(|| anyhow!("day is required"))?;
-                    next_monthly(after, t, day)
+                    next_monthly(after, t, day, gap)
+                }
+                Repeat::MonthlyWeekday => {
+                    let t = parse_hhmm(time.as_deref())?;
+                    let week = week.ok_or_else(|| anyhow!("week is required"))?;
+                    let weekday = weekday.ok_or_else(|| anyhow!("weekday is required"))?;
+                    next_monthly_weekday(after, t, week, weekday, gap)
+                }
+                Repeat::EveryMinute => Some(next_every_minute(after)),
+                Repeat::Hourly => {
+                    let minute = minute.ok_or_else(|| anyhow!("minute is required"))?;
+                    Some(next_hourly(after, minute))
+                }
+                Repeat::Yearly => {
+                    let t = parse_hhmm(time.as_deref())?;
+                    let month = month.ok_or_else(|| anyhow!("month is required"))?;
+                    let day = day.ok_or_else(|| anyhow!("day is required"))?;
+                    next_yearly(after, t, month, day, gap)
                 }
-                Repeat::EveryMinute => next_every_minute(after),
                 Repeat::Once => {
                     let once = once_at
                         .as_deref()
                         .ok_or_else(|| anyhow!("once_at is required"))?;
-                    let naive = NaiveDateTime::parse_from_str(once, "%Y-%m-%d %H:%M")
-                        .map_err(|e| anyhow!("invalid once_at: {e}"))?;
-                    let dt = match Local.from_local_datetime(&naive) {
-                        LocalResult::Single(dt) => dt,
-                        LocalResult::Ambiguous(dt, _) => dt,
-                        LocalResult::None => return Ok(None),
-                    };
-                    if dt > after {
-                        dt
-                    } else {
-                        return Ok(None);
-                    }
+                    parse_once_at(once, gap)?.filter(|dt| *dt > after)
                 }
-            }))
+            })
         }
     }
 }
 
 pub fn schedule_label(job: &JobConfig) -> String {
-    match &job.schedule {
+    let label = match &job.schedule {
         ScheduleConfig::Cron { expression } => format!("cron({expression})"),
+        ScheduleConfig::Interval {
+            every_seconds,
+            delay_seconds,
+        } => match delay_seconds {
+            0 => format!("interval(every {} from start)", seconds_label(*every_seconds)),
+            delay => format!(
+                "interval(every {}, first +{} from start)",
+                seconds_label(*every_seconds),
+                seconds_label(*delay)
+            ),
+        },
         ScheduleConfig::Simple {
             repeat,
             time,
+            times,
             weekday,
             day,
+            week,
             once_at,
+            minute,
+            month,
+            ..
         } => match repeat {
-            Repeat::Daily => format!("daily@{}", time.clone().unwrap_or_else(|| "-".to_string())),
+            Repeat::Daily => {
+                let all: Vec<String> = time.iter().chain(times.iter()).cloned().collect();
+                if all.is_empty() {
+                    "daily@-".to_string()
+                } else {
+                    format!("daily@{}", all.join(","))
+                }
+            }
             Repeat::Weekly => format!(
                 "weekly({})@{}",
-                weekday.unwrap_or(1),
+                num_to_weekday(weekday.unwrap_or(1)),
                 time.clone().unwrap_or_else(|| "-".to_string())
             ),
             Repeat::Monthly => format!(
                 "monthly({})@{}",
-                day.unwrap_or(1),
+                day_label(day.unwrap_or(1)),
+                time.clone().unwrap_or_else(|| "-".to_string())
+            ),
+            Repeat::MonthlyWeekday => format!(
+                "monthly({} {})@{}",
+                week_label(week.unwrap_or(1)),
+                num_to_weekday(weekday.unwrap_or(1)),
                 time.clone().unwrap_or_else(|| "-".to_string())
             ),
             Repeat::EveryMinute => "every-minute".to_string(),
+            Repeat::Hourly => format!("hourly@:{:02}", minute.unwrap_or(0)),
+            Repeat::Yearly => format!(
+                "yearly({:02}-{:02})@{}",
+                month.unwrap_or(1),
+                day.unwrap_or(1),
+                time.clone().unwrap_or_else(|| "-".to_string())
+            ),
             Repeat::Once => format!("once@{}", once_at.clone().unwrap_or_else(|| "-".to_string())),
         },
+    };
+    if job.manual_only {
+        format!("manual-only {label}")
+    } else {
+        label
+    }
+}
+
+// Runs land on anchor + delay + k * every, so the cadence survives restarts that keep the anchor.
+fn next_interval(after: DateTime<Local>, anchor: DateTime<Local>, every: u64, delay: u64) -> Result<DateTime<Local>> {
+    if every == 0 {
+        bail!("every_seconds must be at least 1");
+    }
+    let first = anchor + TimeDelta::seconds(delay as i64);
+    if after < first {
+        return Ok(first);
+    }
+    let steps = (after - first).num_seconds() as u64 / every + 1;
+    Ok(first + TimeDelta::seconds((steps * every) as i64))
+}
+
+pub fn seconds_label(seconds: u64) -> String {
+    let parts = [
+        (seconds / 86_400, "d"),
+        (seconds % 86_400 / 3_600, "h"),
+        (seconds % 3_600 / 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    let label: String = parts.iter().filter(|(v, _)| *v > 0).map(|(v, unit)| format!("{v}{unit}")).collect();
+    if label.is_empty() { "0s".to_string() } else { label }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November",
+    "December",
+];
+// The cron crate numbers days of the week 1-7 starting at Sunday.
+const CRON_WEEKDAY_NAMES: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+
+/// Renders a cron expression as plain English, e.g. `0 0 2 * * *` -> "At 02:00 every day".
+pub fn describe_cron(expression: &str) -> Result<String> {
+    let expression = expression.trim();
+    cron::Schedule::from_str(expression).map_err(|e| anyhow!("invalid cron expression: {e}"))?;
+    let expression = match expression {
+        "@yearly" | "@annually" => "0 0 0 1 1 *",
+        "@monthly" => "0 0 0 1 * *",
+        "@weekly" => "0 0 0 * * 1",
+        "@daily" => "0 0 0 * * *",
+        "@hourly" => "0 0 * * * *",
+        other => other,
+    };
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let field = |idx: usize| fields.get(idx).copied().unwrap_or("*");
+    let is_any = |value: &str| value == "*" || value == "?";
+    let (second, minute, hour) = (field(0), field(1), field(2));
+
+    let mut clauses = Vec::new();
+    let times = clock_times(second, minute, hour);
+    if let Some(times) = &times {
+        clauses.push(format!("at {}", join_and(times)));
+    } else {
+        // A wildcard is implied by a finer field that already repeats ("every 5 minutes", not "..., every hour").
+        if second != "0" {
+            clauses.push(describe_cron_field(second, "second", ("at second", "at seconds"), "", &[], 0));
+        }
+        if !is_any(minute) || is_single(second) {
+            clauses.push(describe_cron_field(minute, "minute", ("at minute", "at minutes"), "", &[], 0));
+        }
+        if !is_any(hour) || is_single(minute) {
+            clauses.push(describe_cron_field(hour, "hour", ("during hour", "during hours"), "", &[], 0));
+        }
+    }
+    let dates = [
+        (field(3), "day", ("on day", "on days"), " of the month", &[][..], 0),
+        (field(4), "month", ("in", "in"), "", &MONTH_NAMES[..], 1),
+        (field(5), "day of the week", ("on", "on"), "", &CRON_WEEKDAY_NAMES[..], 1),
+        (field(6), "year", ("in", "in"), "", &[][..], 0),
+    ];
+    let mut every_day = true;
+    for (value, unit, prefix, suffix, names, first) in dates {
+        if !is_any(value) {
+            every_day = false;
+            clauses.push(describe_cron_field(value, unit, prefix, suffix, names, first));
+        }
+    }
+
+    let mut text = clauses.join(", ");
+    if every_day && times.is_some() {
+        text.push_str(" every day");
+    }
+    let mut chars = text.chars();
+    Ok(match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => text,
+    })
+}
+
+fn is_single(value: &str) -> bool {
+    value.parse::<u32>().is_ok()
+}
+
+fn clock_times(second: &str, minute: &str, hour: &str) -> Option<Vec<String>> {
+    let second: u32 = second.parse().ok()?;
+    let minute: u32 = minute.parse().ok()?;
+    let hours = hour
+        .split(',')
+        .map(|h| h.parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    Some(
+        hours
+            .into_iter()
+            .map(|h| match second {
+                0 => format!("{h:02}:{minute:02}"),
+                s => format!("{h:02}:{minute:02}:{s:02}"),
+            })
+            .collect(),
+    )
+}
+
+fn describe_cron_field(
+    value: &str,
+    unit: &str,
+    (single, plural): (&str, &str),
+    suffix: &str,
+    names: &[&str],
+    first: u32,
+) -> String {
+    let name = |token: &str| -> String {
+        match token.parse::<u32>() {
+            Ok(n) => n
+                .checked_sub(first)
+                .and_then(|idx| names.get(idx as usize))
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| token.to_string()),
+            Err(_) => names
+                .iter()
+                .find(|name| name.to_lowercase().starts_with(&token.to_lowercase()))
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| token.to_string()),
+        }
+    };
+    if value == "*" || value == "?" {
+        return format!("every {unit}");
+    }
+    if let Some((base, step)) = value.split_once('/') {
+        let every = format!("every {step} {unit}s{suffix}");
+        return match base.split_once('-') {
+            _ if base == "*" => every,
+            Some((from, to)) => format!("{every} from {} through {}", name(from), name(to)),
+            None if names.is_empty() => format!("{every} starting at {unit} {base}"),
+            None => format!("{every} starting at {}", name(base)),
+        };
+    }
+    let items: Vec<String> = value
+        .split(',')
+        .map(|item| match item.split_once('-') {
+            Some((from, to)) => format!("{} through {}", name(from), name(to)),
+            None => name(item),
+        })
+        .collect();
+    let prefix = if items.len() == 1 && !value.contains('-') { single } else { plural };
+    format!("{prefix} {}{suffix}", join_and(&items))
+}
+
+fn join_and(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
     }
 }
 
+pub fn parse_once_at(value: &str, gap: DstGap) -> Result<Option<DateTime<Local>>> {
+    parse_local_datetime("once_at", value, gap)
+}
+
+fn parse_local_datetime(field: &str, value: &str, gap: DstGap) -> Result<Option<DateTime<Local>>> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Some(dt.with_timezone(&Local)));
+    }
+    let naive = ["%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(value, fmt).ok())
+        .ok_or_else(|| anyhow!("invalid {field} '{value}', expected YYYY-MM-DD HH:MM or RFC3339"))?;
+    Ok(resolve_local(naive, gap))
+}
+
 fn parse_hhmm(time: Option<&str>) -> Result<NaiveTime> {
     let time = time.ok_or_else(|| anyhow!("time is required"))?;
     NaiveTime::parse_from_str(time, "%H:%M").map_err(|e| anyhow!("invalid time: {e}"))
 }
 
-fn next_daily(after: DateTime<Local>, time: NaiveTime) -> DateTime<Local> {
+fn daily_times(time: Option<&str>, times: &[String]) -> Result<Vec<NaiveTime>> {
+    let mut out = Vec::new();
+    if time.is_some() {
+        out.push(parse_hhmm(time)?);
+    }
+    for t in times {
+        out.push(parse_hhmm(Some(t))?);
+    }
+    if out.is_empty() {
+        return Err(anyhow!("time is required"));
+    }
+    out.sort();
+    out.dedup();
+    Ok(out)
+}
+
+fn next_daily(after: DateTime<Local>, times: &[NaiveTime], gap: DstGap) -> Option<DateTime<Local>> {
     let mut date = after.date_naive();
-    let mut candidate = local_datetime(date.year(), date.month(), date.day(), time);
-    if candidate <= after {
-        date = date
-            .checked_add_days(Days::new(1))
-            .expect("daily overflow should not happen");
-        candidate = local_datetime(date.year(), date.month(), date.day(), time);
+    for _ in 0..3 {
+        for time in times {
+            if let Some(candidate) = resolve_local(date.and_time(*time), gap)
+                && candidate > after
+            {
+                return Some(candidate);
+            }
+        }
+        date = date.checked_add_days(Days::new(1))?;
     }
-    candidate
+    None
 }
 
 fn next_every_minute(after: DateTime<Local>) -> DateTime<Local> {
-    let ts = after + chrono::TimeDelta::minutes(1);
-    ts.with_second(0)
-        .and_then(|v| v.with_nanosecond(0))
-        .unwrap_or(ts)
+    truncate_to_minute(after) + chrono::TimeDelta::minutes(1)
+}
+
+fn next_hourly(after: DateTime<Local>, minute: u8) -> DateTime<Local> {
+    let offset = i64::from(after.minute()) - i64::from(minute);
+    let base = truncate_to_minute(after) - chrono::TimeDelta::minutes(offset);
+    if base > after {
+        base
+    } else {
+        base + chrono::TimeDelta::hours(1)
+    }
+}
+
+// Works on the absolute instant so it never goes through an ambiguous or missing local time.
+fn truncate_to_minute(value: DateTime<Local>) -> DateTime<Local> {
+    value
+        - chrono::TimeDelta::seconds(i64::from(value.second()))
+        - chrono::TimeDelta::nanoseconds(i64::from(value.nanosecond()))
 }
 
-fn next_weekly(after: DateTime<Local>, time: NaiveTime, weekday: u8) -> DateTime<Local> {
+fn next_weekly(after: DateTime<Local>, time: NaiveTime, weekday: u8, gap: DstGap) -> Option<DateTime<Local>> {
     let target = num_to_weekday(weekday);
     let mut date = after.date_naive();
 
-    for _ in 0..8 {
-        if date.weekday() == target {
-            let candidate = local_datetime(date.year(), date.month(), date.day(), time);
-            if candidate > after {
-                return candidate;
-            }
+    for _ in 0..15 {
+        if date.weekday() == target
+            && let Some(candidate) = resolve_local(date.and_time(time), gap)
+            && candidate > after
+        {
+            return Some(candidate);
         }
-        date = date
-            .checked_add_days(Days::new(1))
-            .expect("weekly overflow should not happen");
+        date = date.checked_add_days(Days::new(1))?;
     }
-
-    local_datetime(date.year(), date.month(), date.day(), time)
+    None
 }
 
-fn next_monthly(after: DateTime<Local>, time: NaiveTime, day: u8) -> DateTime<Local> {
+fn next_monthly(after: DateTime<Local>, time: NaiveTime, day: u8, gap: DstGap) -> Option<DateTime<Local>> {
     let mut year = after.year();
     let mut month = after.month();
 
     for _ in 0..24 {
         let max_day = days_in_month(year, month);
-        let target_day = u32::from(day).min(max_day);
-        let candidate = local_datetime(year, month, target_day, time);
-        if candidate > after {
-            return candidate;
+        let target_day = if day == LAST_DAY { max_day } else { u32::from(day).min(max_day) };
+        if let Some(candidate) = local_datetime(year, month, target_day, time, gap)
+            && candidate > after
+        {
+            return Some(candidate);
         }
 
         if month == 12 {
@@ -152,25 +480,85 @@ fn next_monthly(after: DateTime<Local>, time: NaiveTime, day: u8) -> DateTime<Lo
             month += 1;
         }
     }
+    None
+}
+
+fn next_monthly_weekday(
+    after: DateTime<Local>,
+    time: NaiveTime,
+    week: i8,
+    weekday: u8,
+    gap: DstGap,
+) -> Option<DateTime<Local>> {
+    let target = num_to_weekday(weekday);
+    let mut year = after.year();
+    let mut month = after.month();
+
+    for _ in 0..24 {
+        let day = if week == LAST_WEEK {
+            let last = days_in_month(year, month);
+            let last_weekday = NaiveDate::from_ymd_opt(year, month, last)?.weekday();
+            last - (7 + last_weekday.num_days_from_monday() - target.num_days_from_monday()) % 7
+        } else {
+            let first_weekday = NaiveDate::from_ymd_opt(year, month, 1)?.weekday();
+            let first = 1 + (7 + target.num_days_from_monday() - first_weekday.num_days_from_monday()) % 7;
+            first + 7 * (week.max(1) as u32 - 1)
+        };
+        if let Some(candidate) = local_datetime(year, month, day, time, gap)
+            && candidate > after
+        {
+            return Some(candidate);
+        }
 
-    local_datetime(year, month, 1, time)
+        if month == 12 {
+            year += 1;
+            month = 1;
+        } else {
+            month += 1;
+        }
+    }
+    None
 }
 
-fn local_datetime(year: i32, month: u32, day: u32, time: NaiveTime) -> DateTime<Local> {
-    match Local.with_ymd_and_hms(year, month, day, time.hour(), time.minute(), 0) {
-        LocalResult::Single(dt) => dt,
-        LocalResult::Ambiguous(dt, _) => dt,
-        LocalResult::None => {
-            let mut minute = time.minute();
-            while minute < 59 {
-                minute += 1;
-                if let LocalResult::Single(dt) = Local.with_ymd_and_hms(year, month, day, time.hour(), minute, 0) {
-                    return dt;
-                }
-            }
-            Local::now()
+fn next_yearly(after: DateTime<Local>, time: NaiveTime, month: u8, day: u8, gap: DstGap) -> Option<DateTime<Local>> {
+    let month = u32::from(month);
+    for year in after.year()..=after.year() + 8 {
+        let target_day = u32::from(day).min(days_in_month(year, month));
+        if let Some(candidate) = local_datetime(year, month, target_day, time, gap)
+            && candidate > after
+        {
+            return Some(candidate);
         }
     }
+    None
+}
+
+pub fn max_day_of_month(month: u8) -> u8 {
+    days_in_month(2000, u32::from(month)) as u8
+}
+
+fn local_datetime(year: i32, month: u32, day: u32, time: NaiveTime, gap: DstGap) -> Option<DateTime<Local>> {
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
+    resolve_local(date.and_time(time), gap)
+}
+
+// Ambiguous times (clocks going back) use the first occurrence. Times inside a gap (clocks
+// going forward) either move to the first valid minute after the gap or are skipped.
+fn resolve_local(naive: NaiveDateTime, gap: DstGap) -> Option<DateTime<Local>> {
+    match Local.from_local_datetime(&naive) {
+        LocalResult::Single(dt) => Some(dt),
+        LocalResult::Ambiguous(a, b) => Some(a.min(b)),
+        LocalResult::None => match gap {
+            DstGap::Skip => None,
+            DstGap::Shift => (1..=MAX_GAP_MINUTES).find_map(|m| {
+                match Local.from_local_datetime(&(naive + chrono::TimeDelta::minutes(m))) {
+                    LocalResult::Single(dt) => Some(dt),
+                    LocalResult::Ambiguous(a, b) => Some(a.min(b)),
+                    LocalResult::None => None,
+                }
+            }),
+        },
+    }
 }
 
 fn num_to_weekday(v: u8) -> Weekday {
@@ -185,6 +573,90 @@ fn num_to_weekday(v: u8) -> Weekday {
     }
 }
 
+pub fn parse_day(value: &str) -> Result<u8> {
+    let value = value.trim().to_lowercase();
+    if value == "last" {
+        return Ok(LAST_DAY);
+    }
+    value
+        .parse::<u8>()
+        .map_err(|_| anyhow!("invalid day '{value}', expected 1-31 or last"))
+}
+
+pub fn parse_minute(value: &str) -> Result<u8> {
+    let value = value.trim();
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|n| *n <= 59)
+        .ok_or_else(|| anyhow!("invalid minute '{value}', expected 0-59"))
+}
+
+pub fn parse_month(value: &str) -> Result<u8> {
+    let value = value.trim();
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|n| (1..=12).contains(n))
+        .ok_or_else(|| anyhow!("invalid month '{value}', expected 1-12"))
+}
+
+pub fn day_label(day: u8) -> String {
+    if day == LAST_DAY {
+        "last".to_string()
+    } else {
+        day.to_string()
+    }
+}
+
+pub fn parse_week(value: &str) -> Result<i8> {
+    let value = value.trim().to_lowercase();
+    let week = match value.as_str() {
+        "1" | "first" | "1st" => 1,
+        "2" | "second" | "2nd" => 2,
+        "3" | "third" | "3rd" => 3,
+        "4" | "fourth" | "4th" => 4,
+        "-1" | "last" => LAST_WEEK,
+        _ => bail!("invalid week '{value}', expected 1-4 or last"),
+    };
+    Ok(week)
+}
+
+pub fn week_label(week: i8) -> String {
+    match week {
+        1 => "1st".to_string(),
+        2 => "2nd".to_string(),
+        3 => "3rd".to_string(),
+        LAST_WEEK => "last".to_string(),
+        n => format!("{n}th"),
+    }
+}
+
+pub fn parse_weekday(value: &str) -> Result<u8> {
+    let value = value.trim().to_lowercase();
+    if let Ok(n) = value.parse::<u8>() {
+        if !(1..=7).contains(&n) {
+            bail!("weekday must be 1..=7");
+        }
+        return Ok(n);
+    }
+    let n = match value.as_str() {
+        "mon" | "monday" => 1,
+        "tue" | "tuesday" => 2,
+        "wed" | "wednesday" => 3,
+        "thu" | "thursday" => 4,
+        "fri" | "friday" => 5,
+        "sat" | "saturday" => 6,
+        "sun" | "sunday" => 7,
+        _ => bail!("unknown weekday '{value}', expected one of mon, tue, wed, thu, fri, sat, sun or 1-7"),
+    };
+    Ok(n)
+}
+
+pub fn weekday_name(v: u8) -> String {
+    num_to_weekday(v).to_string()
+}
+
 fn days_in_month(year: i32, month: u32) -> u32 {
     let first = chrono::NaiveDate::from_ymd_opt(year, month, 1).expect("valid month");
     let (next_year, next_month) = if month == 12 {
@@ -0,0 +1,152 @@
+use crate::config;
+use crate::daemon;
+use crate::model::{DaemonState, JobConfig};
+use crate::paths::AppPaths;
+use crate::scheduler;
+use crate::tui::load_next_runs;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+struct Snapshot {
+    jobs: Vec<JobConfig>,
+    state: Option<DaemonState>,
+    daemon_pid: Option<i32>,
+    next_runs: HashMap<String, DateTime<Local>>,
+    error: Option<String>,
+}
+
+impl Snapshot {
+    fn load(paths: &AppPaths) -> Self {
+        let daemon_pid = daemon::daemon_running(paths).ok().flatten();
+        let (jobs, error) = match config::load_jobs_skipping_collisions(paths) {
+            Ok(loaded) => (loaded.jobs, None),
+            Err(err) => (Vec::new(), Some(format!("load jobs failed: {err}"))),
+        };
+        Self {
+            next_runs: load_next_runs(paths, daemon_pid, &jobs),
+            state: daemon::read_state(paths).ok(),
+            jobs,
+            daemon_pid,
+            error,
+        }
+    }
+}
+
+pub fn run_top(paths: &AppPaths) -> Result<()> {
+    let plain = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let mut snapshot = Snapshot::load(paths);
+    let mut last_refresh = Instant::now();
+    let mut terminal = ratatui::init();
+    loop {
+        if last_refresh.elapsed() >= Duration::from_secs(1) {
+            snapshot = Snapshot::load(paths);
+            last_refresh = Instant::now();
+        }
+        terminal.draw(|f| render(f, &snapshot, plain))?;
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && (matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+                || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)))
+        {
+            break;
+        }
+    }
+    ratatui::restore();
+    Ok(())
+}
+
+fn render(f: &mut Frame, snapshot: &Snapshot, plain: bool) {
+    let fg = |color: Color| if plain { Style::default() } else { Style::default().fg(color) };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(f.area());
+
+    // Only trust the state's running counts while its daemon is alive; a crash leaves them behind.
+    let state = snapshot.state.as_ref().filter(|s| Some(s.pid as i32) == snapshot.daemon_pid);
+    let running: usize = state.map(|s| s.jobs.iter().map(|j| j.running).sum()).unwrap_or(0);
+    let daemon = match snapshot.daemon_pid {
+        Some(pid) if state.is_some_and(|s| s.paused) => format!("daemon: paused (pid={pid})"),
+        Some(pid) => format!("daemon: running (pid={pid})"),
+        None => "daemon: stopped".to_string(),
+    };
+    let updated = snapshot
+        .state
+        .as_ref()
+        .map(|s| format!("state age: {}s", (Local::now() - s.updated_at).num_seconds().max(0)))
+        .unwrap_or_else(|| "state: none".to_string());
+    let mut header = format!(
+        "{daemon}  running: {running}  jobs: {}  {updated}  q to quit",
+        snapshot.jobs.len()
+    );
+    if let Some(error) = &snapshot.error {
+        header = format!("{header}  {error}");
+    }
+    f.render_widget(
+        Paragraph::new(Line::from(header)).block(Block::default().borders(Borders::ALL).title("macrond top")),
+        chunks[0],
+    );
+
+    let views: HashMap<&str, _> = snapshot
+        .state
+        .iter()
+        .flat_map(|s| s.jobs.iter())
+        .map(|view| (view.id.as_str(), view))
+        .collect();
+    let rows = snapshot.jobs.iter().map(|job| {
+        let view = views.get(job.id.as_str());
+        let last = view.and_then(|v| v.last_result.as_ref());
+        let running = view.filter(|_| state.is_some()).map_or(0, |v| v.running);
+        let status = match (running, last) {
+            (n, _) if n > 0 => Cell::from(format!("running ({n})")).style(fg(Color::Cyan)),
+            (_, Some(last)) => {
+                let color = match last.status.as_str() {
+                    "success" => Color::Green,
+                    "timeout" => Color::Yellow,
+                    _ => Color::Red,
+                };
+                Cell::from(last.status.clone()).style(fg(color))
+            }
+            (_, None) => Cell::from("-"),
+        };
+        let duration = last
+            .map(|r| scheduler::seconds_label((r.ended_at - r.started_at).num_seconds().max(0) as u64))
+            .unwrap_or_else(|| "-".to_string());
+        let next = snapshot
+            .next_runs
+            .get(&job.id)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let enabled = if job.enabled {
+            Cell::from("yes")
+        } else {
+            Cell::from("no").style(fg(Color::DarkGray))
+        };
+        Row::new([Cell::from(job.id.clone()), enabled, Cell::from(next), status, Cell::from(duration)])
+    });
+    let widths = [
+        Constraint::Min(16),
+        Constraint::Length(8),
+        Constraint::Length(20),
+        Constraint::Length(12),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(
+            Row::new(["JOB", "ENABLED", "NEXT RUN", "LAST", "DURATION"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::default().borders(Borders::ALL).title("jobs"));
+    f.render_widget(table, chunks[1]);
+}
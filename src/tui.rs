@@ -1,43 +1,60 @@
 use crate::config;
 use crate::daemon;
-use crate::model::{CommandConfig, JobConfig, Repeat, ScheduleConfig};
-use crate::paths::AppPaths;
+use crate::model::{CommandConfig, DstGap, JobConfig, Repeat, ScheduleConfig};
+use crate::paths::{self, AppPaths};
 use crate::scheduler;
 use anyhow::{Context, Result, bail};
-use chrono::Local;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use chrono::{DateTime, Local};
+use crossterm::event::{self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::{Line, Text};
+use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
 use ratatui::Frame;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use std::process::Command as StdCommand;
+use std::io::{BufRead, BufReader, Read};
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command as StdCommand, Stdio};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::{Duration, Instant};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-pub fn run_tui(paths: &AppPaths) -> Result<()> {
+// How long an `R` run of a job without timeout_seconds is watched before giving up.
+const WATCH_FALLBACK_SECONDS: u64 = 3600;
+
+pub fn run_tui(paths: &AppPaths, plain: bool, read_only: bool) -> Result<()> {
     let mut ui = UiState::load(paths)?;
+    ui.read_only = read_only;
+    ui.theme = Theme {
+        plain: plain || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+    };
     let mut terminal = ratatui::init();
+    crossterm::execute!(std::io::stdout(), EnableBracketedPaste)?;
     let mut last_auto_refresh = Instant::now();
 
     let mut quit = false;
     while !quit {
         if last_auto_refresh.elapsed() >= Duration::from_secs(1) {
             let _ = ui.refresh_runtime(paths);
+            ui.poll_watched_run(paths);
             last_auto_refresh = Instant::now();
         }
+        ui.poll_test_run();
         terminal.draw(|f| render(f, &ui))?;
         if !event::poll(Duration::from_millis(250))? {
             continue;
         }
-        if let Event::Key(key) = event::read()? {
-            quit = ui.on_key(paths, key)?;
+        match event::read()? {
+            Event::Key(key) => quit = ui.on_key(paths, key)?,
+            Event::Paste(text) => ui.on_paste(&text),
+            _ => {}
         }
     }
 
+    let _ = crossterm::execute!(std::io::stdout(), DisableBracketedPaste);
     ratatui::restore();
     Ok(())
 }
@@ -46,11 +63,81 @@ struct UiState {
     jobs: Vec<JobConfig>,
     history_runs: Vec<String>,
     daemon_pid: Option<i32>,
+    daemon_paused: bool,
+    last_status: Option<HashMap<String, String>>,
+    output_tails: HashMap<String, Vec<String>>,
+    outcomes: HashMap<String, Vec<String>>,
+    next_runs: HashMap<String, DateTime<Local>>,
+    watched_run: Option<WatchedRun>,
+    collapsed_groups: HashSet<String>,
+    skipped_files: Option<String>,
     selected: usize,
     history_selected: usize,
     focus: ListFocus,
     message: String,
     mode: UiMode,
+    theme: Theme,
+    read_only: bool,
+    undo: Option<Undo>,
+}
+
+#[derive(Clone, PartialEq)]
+enum ListRow {
+    Group(String),
+    Job(usize),
+}
+
+// A run request submitted to the daemon with `R`; the daemon writes its outcome (or why it
+// refused the request) to `run/results/<wait_id>.json`.
+struct WatchedRun {
+    job_id: String,
+    wait_id: String,
+    give_up_at: DateTime<Local>,
+}
+
+// The last delete or enable/disable in the list, reverted with `u`.
+enum Undo {
+    Delete { job: Box<JobConfig>, path: PathBuf },
+    Toggle { job_ids: Vec<String>, enabled: bool },
+}
+
+#[derive(Copy, Clone, Default)]
+struct Theme {
+    plain: bool,
+}
+
+impl Theme {
+    fn border(self) -> Style {
+        if self.plain {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Cyan)
+        }
+    }
+
+    fn highlight(self) -> Style {
+        if self.plain {
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            Style::default().bg(Color::Blue).fg(Color::White)
+        }
+    }
+
+    fn selection(self) -> Style {
+        if self.plain {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(Color::DarkGray).fg(Color::White)
+        }
+    }
+
+    fn fg(self, color: Color) -> Style {
+        if self.plain {
+            Style::default()
+        } else {
+            Style::default().fg(color)
+        }
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -61,9 +148,23 @@ enum ListFocus {
 
 enum UiMode {
     List,
-    Edit(EditState),
+    Edit(Box<EditState>),
     ConfirmDelete { job_id: String },
     ConfirmDiscard { edit: Box<EditState> },
+    ConfirmTest { job_id: String },
+    ConfirmEnable { job_id: String, reason: String },
+    TestOutput(Box<TestRun>),
+    ViewJson { job_id: String, lines: Vec<String>, scroll: usize },
+}
+
+struct TestRun {
+    job_id: String,
+    child: Child,
+    rx: Receiver<String>,
+    lines: Vec<String>,
+    scroll: usize,
+    follow: bool,
+    exit: Option<String>,
 }
 
 struct EditState {
@@ -107,38 +208,77 @@ enum SuggestKind {
 enum ScheduleKind {
     Cron,
     Simple,
+    Interval,
 }
 
 #[derive(Clone)]
 struct JobForm {
     id: String,
     name: String,
+    description: String,
+    tags: String,
+    group: String,
     enabled: bool,
+    manual_only: bool,
     schedule_kind: ScheduleKind,
     cron_expression: String,
     repeat: Repeat,
     time: String,
     weekday: u8,
     day: u8,
+    week: i8,
+    minute: u8,
+    month: u8,
     once_at: String,
+    every_seconds: String,
+    delay_seconds: String,
     program: String,
     args: String,
     working_dir: String,
     env_json: String,
     timeout_seconds: String,
+    base_command: CommandConfig,
+    on_success: Option<CommandConfig>,
+    on_failure: Option<CommandConfig>,
+    on_slow: Option<CommandConfig>,
+    ping_url_on_success: Option<String>,
+    ping_url_on_start: Option<String>,
+    warn_after_seconds: Option<u64>,
+    slow_anomaly_factor: Option<f64>,
+    dst_gap: DstGap,
+    max_manual_runs: Option<u32>,
+    disable_after_failures: Option<u32>,
+    active_from: Option<String>,
+    active_until: Option<String>,
+    allow_overlap: bool,
+    log_successes: bool,
+    delete_after_run: bool,
+    output_tail_lines: Option<usize>,
+    created_at: Option<chrono::DateTime<Local>>,
+    completed_at: Option<chrono::DateTime<Local>>,
+    extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 enum EditField {
     Name,
+    Description,
+    Tags,
+    Group,
     Enabled,
+    ManualOnly,
     ScheduleKind,
     CronExpression,
     Repeat,
     Time,
     Weekday,
     Day,
+    Week,
+    Minute,
+    Month,
     OnceAt,
+    Every,
+    Delay,
     Program,
     Args,
     WorkingDir,
@@ -148,30 +288,43 @@ enum EditField {
 
 impl UiState {
     fn load(paths: &AppPaths) -> Result<Self> {
-        let jobs = config::load_jobs(&paths.jobs_dir).unwrap_or_default();
+        let (jobs, skipped_files) = load_jobs(paths).unwrap_or_default();
         let history_runs = load_history_runs(&paths.logs_dir).unwrap_or_default();
         let daemon_pid = daemon::daemon_running(paths).ok().flatten();
+        let daemon_paused = is_daemon_paused(paths, daemon_pid);
         Ok(Self {
-            jobs,
             history_runs,
             daemon_pid,
+            daemon_paused,
+            last_status: load_last_status(paths),
+            output_tails: load_output_tails(paths),
+            outcomes: load_outcomes(paths),
+            next_runs: load_next_runs(paths, daemon_pid, &jobs),
+            watched_run: None,
+            collapsed_groups: HashSet::new(),
+            skipped_files,
+            jobs,
             selected: 0,
             history_selected: 0,
             focus: ListFocus::Jobs,
             message: "Ready".to_string(),
             mode: UiMode::List,
+            theme: Theme::default(),
+            read_only: false,
+            undo: None,
         })
     }
 
     fn reload(&mut self, paths: &AppPaths) -> Result<()> {
-        self.jobs = config::load_jobs(&paths.jobs_dir).context("reload jobs failed")?;
+        (self.jobs, self.skipped_files) = load_jobs(paths).context("reload jobs failed")?;
         self.history_runs = load_history_runs(&paths.logs_dir).unwrap_or_default();
         self.daemon_pid = daemon::daemon_running(paths).ok().flatten();
-        if self.jobs.is_empty() {
-            self.selected = 0;
-        } else if self.selected >= self.jobs.len() {
-            self.selected = self.jobs.len() - 1;
-        }
+        self.daemon_paused = is_daemon_paused(paths, self.daemon_pid);
+        self.last_status = load_last_status(paths);
+        self.output_tails = load_output_tails(paths);
+        self.outcomes = load_outcomes(paths);
+        self.next_runs = load_next_runs(paths, self.daemon_pid, &self.jobs);
+        self.clamp_selected();
         if self.history_runs.is_empty() {
             self.history_selected = 0;
         } else if self.history_selected >= self.history_runs.len() {
@@ -183,12 +336,13 @@ impl UiState {
     fn refresh_runtime(&mut self, paths: &AppPaths) -> Result<()> {
         self.history_runs = load_history_runs(&paths.logs_dir).unwrap_or_default();
         self.daemon_pid = daemon::daemon_running(paths).ok().flatten();
-        self.jobs = config::load_jobs(&paths.jobs_dir).context("refresh jobs failed")?;
-        if self.jobs.is_empty() {
-            self.selected = 0;
-        } else if self.selected >= self.jobs.len() {
-            self.selected = self.jobs.len() - 1;
-        }
+        self.daemon_paused = is_daemon_paused(paths, self.daemon_pid);
+        self.last_status = load_last_status(paths);
+        self.output_tails = load_output_tails(paths);
+        self.outcomes = load_outcomes(paths);
+        self.next_runs = load_next_runs(paths, self.daemon_pid, &self.jobs);
+        (self.jobs, self.skipped_files) = load_jobs(paths).context("refresh jobs failed")?;
+        self.clamp_selected();
         if self.history_runs.is_empty() {
             self.history_selected = 0;
         } else if self.history_selected >= self.history_runs.len() {
@@ -197,17 +351,76 @@ impl UiState {
         Ok(())
     }
 
+    // Grouped jobs are listed under a header row at the position of the group's first member.
+    fn rows(&self) -> Vec<ListRow> {
+        let mut rows = Vec::new();
+        let mut seen = HashSet::new();
+        for (idx, job) in self.jobs.iter().enumerate() {
+            let Some(group) = &job.group else {
+                rows.push(ListRow::Job(idx));
+                continue;
+            };
+            if !seen.insert(group.as_str()) {
+                continue;
+            }
+            rows.push(ListRow::Group(group.clone()));
+            if !self.collapsed_groups.contains(group) {
+                rows.extend(
+                    self.jobs
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, j)| j.group.as_ref() == Some(group))
+                        .map(|(i, _)| ListRow::Job(i)),
+                );
+            }
+        }
+        rows
+    }
+
+    fn clamp_selected(&mut self) {
+        let len = self.rows().len();
+        if len == 0 {
+            self.selected = 0;
+        } else if self.selected >= len {
+            self.selected = len - 1;
+        }
+    }
+
+    fn select_job(&mut self, job_id: &str) {
+        if let Some(group) = self.jobs.iter().find(|j| j.id == job_id).and_then(|j| j.group.clone()) {
+            self.collapsed_groups.remove(&group);
+        }
+        let rows = self.rows();
+        if let Some(pos) = rows
+            .iter()
+            .position(|row| matches!(row, ListRow::Job(idx) if self.jobs[*idx].id == job_id))
+        {
+            self.selected = pos;
+        }
+    }
+
     fn selected_job(&self) -> Option<&JobConfig> {
-        self.jobs.get(self.selected)
+        match self.rows().get(self.selected) {
+            Some(ListRow::Job(idx)) => self.jobs.get(*idx),
+            _ => None,
+        }
+    }
+
+    fn selected_group(&self) -> Option<String> {
+        match self.rows().get(self.selected) {
+            Some(ListRow::Group(group)) => Some(group.clone()),
+            _ => None,
+        }
     }
 
     fn next(&mut self) {
         match self.focus {
             ListFocus::Jobs => {
-                if self.jobs.is_empty() {
+                let len = self.rows().len();
+                if len == 0 {
                     return;
                 }
-                self.selected = (self.selected + 1) % self.jobs.len();
+                self.selected = (self.selected + 1) % len;
             }
             ListFocus::History => {
                 if self.history_runs.is_empty() {
@@ -221,11 +434,12 @@ impl UiState {
     fn previous(&mut self) {
         match self.focus {
             ListFocus::Jobs => {
-                if self.jobs.is_empty() {
+                let len = self.rows().len();
+                if len == 0 {
                     return;
                 }
                 if self.selected == 0 {
-                    self.selected = self.jobs.len() - 1;
+                    self.selected = len - 1;
                 } else {
                     self.selected -= 1;
                 }
@@ -248,13 +462,99 @@ impl UiState {
         match mode {
             UiMode::List => self.on_key_list(paths, key),
             UiMode::ConfirmDelete { job_id } => self.on_key_confirm_delete(paths, key, job_id),
-            UiMode::ConfirmDiscard { edit } => self.on_key_confirm_discard(key, *edit),
+            UiMode::ConfirmDiscard { edit } => self.on_key_confirm_discard(key, edit),
             UiMode::Edit(edit) => self.on_key_edit(paths, key, edit),
+            UiMode::ConfirmTest { job_id } => self.on_key_confirm_test(paths, key, job_id),
+            UiMode::ConfirmEnable { job_id, reason } => self.on_key_confirm_enable(paths, key, job_id, reason),
+            UiMode::TestOutput(run) => self.on_key_test_output(key, run),
+            UiMode::ViewJson { job_id, lines, scroll } => {
+                self.on_key_view_json(key, job_id, lines, scroll)
+            }
         }
     }
 
+    fn on_paste(&mut self, text: &str) {
+        let UiMode::Edit(edit) = &mut self.mode else {
+            return;
+        };
+        let Some(InputState {
+            field,
+            kind: InputKind::Text { value, cursor, suggest },
+        }) = &mut edit.input
+        else {
+            return;
+        };
+        // Inputs are single-line: join pasted lines with spaces.
+        let text = text
+            .trim_end_matches(['\r', '\n'])
+            .replace("\r\n", " ")
+            .replace(['\r', '\n'], " ");
+        value.insert_str(*cursor, &text);
+        *cursor += text.len();
+        *suggest = suggest_for_input(*field, value, &edit.form.working_dir);
+        if *field == EditField::CronExpression {
+            edit.message = cron_feedback(value);
+        }
+    }
+
+    fn poll_test_run(&mut self) {
+        let UiMode::TestOutput(run) = &mut self.mode else {
+            return;
+        };
+        while let Ok(line) = run.rx.try_recv() {
+            run.lines.push(line);
+        }
+        if run.exit.is_none()
+            && let Ok(Some(status)) = run.child.try_wait()
+        {
+            run.exit = Some(if status.success() {
+                "finished".to_string()
+            } else {
+                format!("failed ({status})")
+            });
+        }
+    }
+
+    fn poll_watched_run(&mut self, paths: &AppPaths) {
+        let Some(watch) = &self.watched_run else {
+            return;
+        };
+        let record = match daemon::take_run_result(paths, &watch.wait_id) {
+            Ok(record) => record,
+            Err(err) => {
+                self.message = format!("Reading result of {} failed: {err:#}", watch.job_id);
+                self.watched_run = None;
+                return;
+            }
+        };
+        if let Some(record) = record.as_ref().filter(|r| r.status == "skipped") {
+            self.message = format!("Run of {} was not started: {}", record.job_id, record.message);
+        } else if let Some(record) = record {
+            let exit = record.exit_code.map(|c| format!(" exit_code={c}")).unwrap_or_default();
+            self.message = format!(
+                "Run {} finished: {}{exit} in {}s (run_id={})",
+                record.job_id,
+                record.status,
+                (record.ended_at - record.started_at).num_seconds(),
+                record.run_id
+            );
+        } else if self.daemon_pid.is_none() {
+            self.message = format!("Daemon stopped before run of {} finished", watch.job_id);
+        } else if Local::now() > watch.give_up_at {
+            self.message = format!("No result for {} yet, check daemon/job logs", watch.job_id);
+        } else {
+            return;
+        }
+        self.watched_run = None;
+    }
+
     fn on_key_list(&mut self, paths: &AppPaths, key: KeyEvent) -> Result<bool> {
         self.daemon_pid = daemon::daemon_running(paths).ok().flatten();
+        let edits_job = key.code == KeyCode::Enter && self.focus == ListFocus::Jobs && self.selected_group().is_none();
+        if self.read_only && (edits_job || matches!(key.code, KeyCode::Char('a' | 'e' | 'd' | 's' | 'u' | 't' | 'R' | 'S' | 'X'))) {
+            self.message = "read-only mode: editing, running jobs and daemon control are disabled (v views JSON)".to_string();
+            return Ok(false);
+        }
         match key.code {
             KeyCode::Char('q') => return Ok(true),
             KeyCode::Char('j') | KeyCode::Down => self.next(),
@@ -277,29 +577,56 @@ impl UiState {
                     return Ok(false);
                 }
                 let mut id = generate_job_id();
-                while job_file_path(&paths.jobs_dir, &id).exists() {
+                while config::job_file_path(&paths.jobs_dir, &id).exists() {
                     id = generate_job_id();
                 }
-                self.mode = UiMode::Edit(EditState::new(JobForm::new(id), "Creating new job"));
+                let defaults = config::load_defaults(&paths.defaults_file).ok().flatten();
+                self.mode = UiMode::Edit(Box::new(EditState::new(
+                    JobForm::new(id, defaults.as_ref()),
+                    "Creating new job",
+                )));
             }
             KeyCode::Char('s') => {
                 if self.focus != ListFocus::Jobs {
                     self.message = "Switch focus to Jobs to toggle job".to_string();
                     return Ok(false);
                 }
-                if let Some(job_id) = self.selected_job().map(|j| j.id.clone()) {
-                    let current = load_job_by_id(&paths.jobs_dir, &job_id)?;
-                    let next_enabled = !current.enabled;
-                    set_job_enabled(paths, &job_id, next_enabled)?;
+                if let Some(group) = self.selected_group() {
+                    // Enable the whole group unless every member is already on.
+                    let enabled = !self
+                        .jobs
+                        .iter()
+                        .filter(|j| j.group.as_ref() == Some(&group))
+                        .all(|j| j.enabled);
+                    let config::GroupUpdate { changed, failed } =
+                        config::set_group_enabled(&paths.jobs_dir, &self.jobs, &group, enabled)?;
                     self.reload(paths)?;
-                    if next_enabled {
-                        if self.daemon_pid.is_some() {
-                            self.message = format!("Started job {job_id}");
-                        } else {
-                            self.message = format!("Started job {job_id}, but daemon is stopped");
-                        }
-                    } else {
-                        self.message = format!("Stopped job {job_id}");
+                    if !changed.is_empty() {
+                        self.undo = Some(Undo::Toggle {
+                            job_ids: changed.clone(),
+                            enabled: !enabled,
+                        });
+                    }
+                    let action = if enabled { "Enabled" } else { "Disabled" };
+                    self.message = match failed.first() {
+                        None => format!("{action} {} jobs in group {group}", changed.len()),
+                        Some((id, err)) => format!(
+                            "{action} {} jobs in group {group}, {} failed ({id}: {err:#})",
+                            changed.len(),
+                            failed.len()
+                        ),
+                    };
+                } else if let Some(job_id) = self.selected_job().map(|j| j.id.clone()) {
+                    let mut current = load_job_by_id(&paths.jobs_dir, &job_id)?;
+                    if current.enabled {
+                        self.toggle_job(paths, &job_id, false)?;
+                        return Ok(false);
+                    }
+                    // Catch schedules that would never fire while the user can still fix them.
+                    current.enabled = true;
+                    match config::schedule_warnings(&current, Local::now()).into_iter().next() {
+                        Some(reason) => self.mode = UiMode::ConfirmEnable { job_id, reason },
+                        None => self.toggle_job(paths, &job_id, true)?,
                     }
                 } else {
                     self.message = "No job selected".to_string();
@@ -311,7 +638,48 @@ impl UiState {
                     return Ok(false);
                 }
                 if let Some(job_id) = self.selected_job().map(|j| j.id.clone()) {
-                    self.message = run_test(paths, &job_id)?;
+                    self.mode = UiMode::ConfirmTest { job_id };
+                } else {
+                    self.message = "No job selected".to_string();
+                }
+            }
+            KeyCode::Char('R') => {
+                if self.focus != ListFocus::Jobs {
+                    self.message = "Switch focus to Jobs to run job".to_string();
+                    return Ok(false);
+                }
+                let Some(job) = self.selected_job() else {
+                    self.message = "No job selected".to_string();
+                    return Ok(false);
+                };
+                if self.daemon_pid.is_none() {
+                    self.message = "Daemon is stopped, press S to start it or t to test inline".to_string();
+                    return Ok(false);
+                }
+                // Refusals come back as results too; the deadline only covers a lost result.
+                let limit = if job.timeout_seconds > 0 { job.timeout_seconds + 60 } else { WATCH_FALLBACK_SECONDS };
+                let give_up_at = Local::now() + chrono::TimeDelta::seconds(limit as i64);
+                let job_id = job.id.clone();
+                let wait_id = daemon::submit_waiting_run_request(paths, &job_id)?;
+                self.message = format!("Run of {job_id} submitted, waiting...");
+                self.watched_run = Some(WatchedRun {
+                    job_id,
+                    wait_id,
+                    give_up_at,
+                });
+            }
+            KeyCode::Char('v') => {
+                if self.focus != ListFocus::Jobs {
+                    self.message = "Switch focus to Jobs to view job JSON".to_string();
+                    return Ok(false);
+                }
+                if let Some(job) = self.selected_job() {
+                    let json = serde_json::to_string_pretty(job)?;
+                    self.mode = UiMode::ViewJson {
+                        job_id: job.id.clone(),
+                        lines: json.lines().map(str::to_string).collect(),
+                        scroll: 0,
+                    };
                 } else {
                     self.message = "No job selected".to_string();
                 }
@@ -330,15 +698,22 @@ impl UiState {
                     return Ok(false);
                 }
                 if let Some(job) = self.selected_job() {
-                    self.mode = UiMode::Edit(EditState::new(JobForm::from_job(job), "Editing job"));
+                    self.mode = UiMode::Edit(Box::new(EditState::new(JobForm::from_job(job), "Editing job")));
                 } else {
                     self.message = "No job selected".to_string();
                 }
             }
+            KeyCode::Enter | KeyCode::Char(' ') if self.focus == ListFocus::Jobs && self.selected_group().is_some() => {
+                if let Some(group) = self.selected_group()
+                    && !self.collapsed_groups.remove(&group)
+                {
+                    self.collapsed_groups.insert(group);
+                }
+            }
             KeyCode::Enter => {
                 if self.focus == ListFocus::Jobs {
                     if let Some(job) = self.selected_job() {
-                        self.mode = UiMode::Edit(EditState::new(JobForm::from_job(job), "Editing job"));
+                        self.mode = UiMode::Edit(Box::new(EditState::new(JobForm::from_job(job), "Editing job")));
                     } else {
                         self.message = "No job selected".to_string();
                     }
@@ -350,6 +725,10 @@ impl UiState {
                         .unwrap_or_else(|| "No history line selected".to_string());
                 }
             }
+            KeyCode::Char('u') => match self.undo.take() {
+                Some(undo) => self.apply_undo(paths, undo)?,
+                None => self.message = "Nothing to undo".to_string(),
+            },
             KeyCode::Char('d') => {
                 if self.focus != ListFocus::Jobs {
                     self.message = "Switch focus to Jobs to delete job".to_string();
@@ -371,11 +750,17 @@ impl UiState {
     fn on_key_confirm_delete(&mut self, paths: &AppPaths, key: KeyEvent, job_id: String) -> Result<bool> {
         match key.code {
             KeyCode::Char('y') => {
-                let path = job_file_path(&paths.jobs_dir, &job_id);
+                let path = config::job_file_path(&paths.jobs_dir, &job_id);
                 if path.exists() {
-                    fs::remove_file(path)?;
+                    // Keep the file as written (without defaults.json merged in) so undo restores it as-is.
+                    let deleted = load_job_by_id(&paths.jobs_dir, &job_id).ok();
+                    config::remove_job_file(&path)?;
                     self.reload(paths)?;
                     self.message = format!("Deleted job {job_id}");
+                    if let Some(job) = deleted {
+                        self.undo = Some(Undo::Delete { job: Box::new(job), path });
+                        self.message.push_str(" (u to undo)");
+                    }
                 } else {
                     self.message = format!("Job file not found for {job_id}");
                 }
@@ -390,7 +775,157 @@ impl UiState {
         Ok(false)
     }
 
-    fn on_key_confirm_discard(&mut self, key: KeyEvent, edit: EditState) -> Result<bool> {
+    fn on_key_confirm_test(&mut self, paths: &AppPaths, key: KeyEvent, job_id: String) -> Result<bool> {
+        match key.code {
+            KeyCode::Char('y') => match start_test_run(paths, &job_id) {
+                Ok(run) => {
+                    self.mode = UiMode::TestOutput(Box::new(run));
+                    self.message = format!("Testing job {job_id}");
+                }
+                Err(err) => {
+                    self.message = format!("Test failed for {job_id}: {err:#}");
+                }
+            },
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.message = "Test canceled".to_string();
+            }
+            _ => {
+                self.mode = UiMode::ConfirmTest { job_id };
+            }
+        }
+        Ok(false)
+    }
+
+    fn on_key_confirm_enable(&mut self, paths: &AppPaths, key: KeyEvent, job_id: String, reason: String) -> Result<bool> {
+        match key.code {
+            KeyCode::Char('y') => self.toggle_job(paths, &job_id, true)?,
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.message = format!("Job {job_id} left disabled");
+            }
+            _ => {
+                self.mode = UiMode::ConfirmEnable { job_id, reason };
+            }
+        }
+        Ok(false)
+    }
+
+    fn toggle_job(&mut self, paths: &AppPaths, job_id: &str, enabled: bool) -> Result<()> {
+        config::set_job_enabled(&paths.jobs_dir, job_id, enabled)?;
+        self.undo = Some(Undo::Toggle {
+            job_ids: vec![job_id.to_string()],
+            enabled: !enabled,
+        });
+        self.reload(paths)?;
+        self.message = if !enabled {
+            format!("Stopped job {job_id}")
+        } else if self.daemon_pid.is_some() {
+            format!("Started job {job_id}")
+        } else {
+            format!("Started job {job_id}, but daemon is stopped")
+        };
+        Ok(())
+    }
+
+    fn apply_undo(&mut self, paths: &AppPaths, undo: Undo) -> Result<()> {
+        match undo {
+            Undo::Delete { mut job, path } => {
+                if config::job_file_path(&paths.jobs_dir, &job.id).exists() {
+                    self.message = format!("Cannot undo delete of {}: a job with that id exists again", job.id);
+                    return Ok(());
+                }
+                // Back to the subfolder it was deleted from, not the top level.
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                job.updated_at = Some(Local::now());
+                config::write_job_file(&path, &job)?;
+                self.reload(paths)?;
+                self.select_job(&job.id);
+                self.message = format!("Undid delete of {}", job.id);
+            }
+            Undo::Toggle { job_ids, enabled } => {
+                for job_id in &job_ids {
+                    config::set_job_enabled(&paths.jobs_dir, job_id, enabled)?;
+                }
+                self.reload(paths)?;
+                let action = if enabled { "disable" } else { "enable" };
+                self.message = format!("Undid {action} of {}", job_ids.join(", "));
+            }
+        }
+        Ok(())
+    }
+
+    fn on_key_test_output(&mut self, key: KeyEvent, mut run: Box<TestRun>) -> Result<bool> {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                run.scroll = (run.scroll + 1).min(run.lines.len().saturating_sub(1));
+                run.follow = false;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                run.scroll = run.scroll.saturating_sub(1);
+                run.follow = false;
+            }
+            KeyCode::Char('g') => {
+                run.scroll = 0;
+                run.follow = false;
+            }
+            KeyCode::Char('G') => {
+                run.follow = true;
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                if run.exit.is_none() {
+                    let _ = nix::sys::signal::killpg(
+                        nix::unistd::Pid::from_raw(run.child.id() as i32),
+                        nix::sys::signal::Signal::SIGKILL,
+                    );
+                    let _ = run.child.wait();
+                    self.message = format!("Test for {} killed", run.job_id);
+                } else {
+                    let summary = run
+                        .lines
+                        .iter()
+                        .rev()
+                        .find(|l| l.starts_with("mode=inline"))
+                        .cloned();
+                    self.message = match summary {
+                        Some(line) => format!("Test result: {line}"),
+                        None => format!("Test for {} {}", run.job_id, run.exit.as_deref().unwrap_or("")),
+                    };
+                }
+                return Ok(false);
+            }
+            _ => {}
+        }
+        self.mode = UiMode::TestOutput(run);
+        Ok(false)
+    }
+
+    fn on_key_view_json(
+        &mut self,
+        key: KeyEvent,
+        job_id: String,
+        lines: Vec<String>,
+        mut scroll: usize,
+    ) -> Result<bool> {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => scroll = (scroll + 1).min(lines.len().saturating_sub(1)),
+            KeyCode::Char('k') | KeyCode::Up => scroll = scroll.saturating_sub(1),
+            KeyCode::Char('g') => scroll = 0,
+            KeyCode::Char('G') => scroll = lines.len().saturating_sub(1),
+            KeyCode::Char('y') => {
+                self.message = match copy_to_clipboard(&lines.join("\n")) {
+                    Ok(()) => format!("Copied JSON of {job_id} to clipboard"),
+                    Err(err) => format!("Copy failed: {err:#}"),
+                };
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            _ => {}
+        }
+        self.mode = UiMode::ViewJson { job_id, lines, scroll };
+        Ok(false)
+    }
+
+    fn on_key_confirm_discard(&mut self, key: KeyEvent, edit: Box<EditState>) -> Result<bool> {
         match key.code {
             KeyCode::Char('y') => {
                 self.mode = UiMode::List;
@@ -404,7 +939,7 @@ impl UiState {
         Ok(false)
     }
 
-    fn on_key_edit(&mut self, paths: &AppPaths, key: KeyEvent, mut edit: EditState) -> Result<bool> {
+    fn on_key_edit(&mut self, paths: &AppPaths, key: KeyEvent, mut edit: Box<EditState>) -> Result<bool> {
         if let Some(mut input) = edit.input.take() {
             match &mut input.kind {
                 InputKind::Text {
@@ -420,42 +955,42 @@ impl UiState {
                         edit.input = Some(input);
                     }
                     KeyCode::Down => {
-                        if let Some(state) = suggest.as_mut() {
-                            if !state.options.is_empty() {
-                                state.selected = (state.selected + 1) % state.options.len();
-                                edit.input = Some(input);
-                                self.mode = UiMode::Edit(edit);
-                                return Ok(false);
-                            }
+                        if let Some(state) = suggest.as_mut()
+                            && !state.options.is_empty()
+                        {
+                            state.selected = (state.selected + 1) % state.options.len();
+                            edit.input = Some(input);
+                            self.mode = UiMode::Edit(edit);
+                            return Ok(false);
                         }
                         edit.input = Some(input);
                     }
                     KeyCode::Up => {
-                        if let Some(state) = suggest.as_mut() {
-                            if !state.options.is_empty() {
-                                if state.selected == 0 {
-                                    state.selected = state.options.len() - 1;
-                                } else {
-                                    state.selected -= 1;
-                                }
-                                edit.input = Some(input);
-                                self.mode = UiMode::Edit(edit);
-                                return Ok(false);
+                        if let Some(state) = suggest.as_mut()
+                            && !state.options.is_empty()
+                        {
+                            if state.selected == 0 {
+                                state.selected = state.options.len() - 1;
+                            } else {
+                                state.selected -= 1;
                             }
+                            edit.input = Some(input);
+                            self.mode = UiMode::Edit(edit);
+                            return Ok(false);
                         }
                         edit.input = Some(input);
                     }
                     KeyCode::Enter => {
-                        if let Some(state) = suggest.as_ref() {
-                            if !state.options.is_empty() {
-                                let chosen = state.options[state.selected].clone();
-                                apply_suggestion(value, state, &chosen);
-                                *cursor = value.len();
-                                *suggest = suggest_for_input(input.field, value, &edit.form.working_dir);
-                                edit.input = Some(input);
-                                self.mode = UiMode::Edit(edit);
-                                return Ok(false);
-                            }
+                        if let Some(state) = suggest.as_ref()
+                            && !state.options.is_empty()
+                        {
+                            let chosen = state.options[state.selected].clone();
+                            apply_suggestion(value, state, &chosen);
+                            *cursor = value.len();
+                            *suggest = suggest_for_input(input.field, value, &edit.form.working_dir);
+                            edit.input = Some(input);
+                            self.mode = UiMode::Edit(edit);
+                            return Ok(false);
                         }
                         edit.apply_input(input.field, value.clone());
                     }
@@ -468,14 +1003,10 @@ impl UiState {
                         }
                     }
                     KeyCode::Backspace => {
-                        let removed_char = if *cursor > 0 && *cursor <= value.len() {
-                            value.chars().nth(*cursor - 1)
-                        } else {
-                            None
-                        };
-                        if *cursor > 0 && *cursor <= value.len() {
-                            value.remove(*cursor - 1);
-                            *cursor -= 1;
+                        let removed_char = value.get(..*cursor).and_then(|v| v.chars().next_back());
+                        if let Some(ch) = removed_char {
+                            *cursor -= ch.len_utf8();
+                            value.remove(*cursor);
                         }
                         if let Some(ch) = removed_char {
                             if should_cancel_suggest_on_delete(suggest.as_ref(), ch) {
@@ -489,21 +1020,21 @@ impl UiState {
                         edit.input = Some(input);
                     }
                     KeyCode::Left => {
-                        if *cursor > 0 {
-                            *cursor -= 1;
+                        if let Some(ch) = value.get(..*cursor).and_then(|v| v.chars().next_back()) {
+                            *cursor -= ch.len_utf8();
                         }
                         edit.input = Some(input);
                     }
                     KeyCode::Right => {
-                        if *cursor < value.len() {
-                            *cursor += 1;
+                        if let Some(ch) = value.get(*cursor..).and_then(|v| v.chars().next()) {
+                            *cursor += ch.len_utf8();
                         }
                         edit.input = Some(input);
                     }
                     KeyCode::Char(c) => {
                         if *cursor <= value.len() {
                             value.insert(*cursor, c);
-                            *cursor += 1;
+                            *cursor += c.len_utf8();
                         }
                         *suggest = suggest_for_input(input.field, value, &edit.form.working_dir);
                         edit.input = Some(input);
@@ -536,6 +1067,13 @@ impl UiState {
                     }
                 },
             }
+            if let Some(InputState {
+                field: EditField::CronExpression,
+                kind: InputKind::Text { value, .. },
+            }) = &edit.input
+            {
+                edit.message = cron_feedback(value);
+            }
             self.mode = UiMode::Edit(edit);
             return Ok(false);
         }
@@ -544,15 +1082,14 @@ impl UiState {
             KeyCode::Char('j') | KeyCode::Down => edit.next_field(),
             KeyCode::Char('k') | KeyCode::Up => edit.prev_field(),
             KeyCode::Enter => edit.activate_field(),
-            KeyCode::Char('s') => match edit.to_job() {
+            KeyCode::Char('s') => match edit
+                .to_job()
+                .and_then(|job| config::check_job_working_dirs(&paths.base_dir, &job).map(|()| job))
+            {
                 Ok(job) => {
                     write_job(paths, &job)?;
                     self.reload(paths)?;
-                    self.selected = self
-                        .jobs
-                        .iter()
-                        .position(|j| j.id == job.id)
-                        .unwrap_or(self.selected);
+                    self.select_job(&job.id);
                     self.mode = UiMode::List;
                     self.message = format!("Saved job {}", job.id);
                     return Ok(false);
@@ -563,9 +1100,7 @@ impl UiState {
             },
             KeyCode::Char('q') | KeyCode::Esc => {
                 if edit.dirty {
-                    self.mode = UiMode::ConfirmDiscard {
-                        edit: Box::new(edit),
-                    };
+                    self.mode = UiMode::ConfirmDiscard { edit };
                     return Ok(false);
                 }
                 self.mode = UiMode::List;
@@ -592,9 +1127,18 @@ impl EditState {
     }
 
     fn fields(&self) -> Vec<EditField> {
-        let mut fields = vec![EditField::Name, EditField::Enabled, EditField::ScheduleKind];
+        let mut fields = vec![
+            EditField::Name,
+            EditField::Description,
+            EditField::Tags,
+            EditField::Group,
+            EditField::Enabled,
+            EditField::ManualOnly,
+            EditField::ScheduleKind,
+        ];
         match self.form.schedule_kind {
             ScheduleKind::Cron => fields.push(EditField::CronExpression),
+            ScheduleKind::Interval => fields.extend([EditField::Every, EditField::Delay]),
             ScheduleKind::Simple => {
                 fields.push(EditField::Repeat);
                 match self.form.repeat {
@@ -607,7 +1151,18 @@ impl EditState {
                         fields.push(EditField::Day);
                         fields.push(EditField::Time);
                     }
+                    Repeat::MonthlyWeekday => {
+                        fields.push(EditField::Week);
+                        fields.push(EditField::Weekday);
+                        fields.push(EditField::Time);
+                    }
                     Repeat::EveryMinute => {}
+                    Repeat::Hourly => fields.push(EditField::Minute),
+                    Repeat::Yearly => {
+                        fields.push(EditField::Month);
+                        fields.push(EditField::Day);
+                        fields.push(EditField::Time);
+                    }
                     Repeat::Once => fields.push(EditField::OnceAt),
                 }
             }
@@ -659,10 +1214,16 @@ impl EditState {
                 self.dirty = true;
                 self.message = format!("enabled={}", self.form.enabled);
             }
+            EditField::ManualOnly => {
+                self.form.manual_only = !self.form.manual_only;
+                self.dirty = true;
+                self.message = format!("manual_only={}", self.form.manual_only);
+            }
             EditField::ScheduleKind => {
                 self.form.schedule_kind = match self.form.schedule_kind {
                     ScheduleKind::Cron => ScheduleKind::Simple,
-                    ScheduleKind::Simple => ScheduleKind::Cron,
+                    ScheduleKind::Simple => ScheduleKind::Interval,
+                    ScheduleKind::Interval => ScheduleKind::Cron,
                 };
                 self.dirty = true;
                 self.selected = 0;
@@ -673,7 +1234,10 @@ impl EditState {
                     "daily".to_string(),
                     "weekly".to_string(),
                     "monthly".to_string(),
+                    "monthlyweekday".to_string(),
                     "everyminute".to_string(),
+                    "hourly".to_string(),
+                    "yearly".to_string(),
                     "once".to_string(),
                 ];
                 let current = options
@@ -693,6 +1257,11 @@ impl EditState {
                 let value = self.field_value(field);
                 let cursor = value.len();
                 let suggest = suggest_for_input(field, &value, &self.form.working_dir);
+                self.message = if field == EditField::CronExpression {
+                    cron_feedback(&value)
+                } else {
+                    "Editing field... Enter=apply Esc=cancel".to_string()
+                };
                 self.input = Some(InputState {
                     field,
                     kind: InputKind::Text {
@@ -701,7 +1270,6 @@ impl EditState {
                         suggest,
                     },
                 });
-                self.message = "Editing field... Enter=apply Esc=cancel".to_string();
             }
         }
     }
@@ -709,48 +1277,107 @@ impl EditState {
     fn apply_input(&mut self, field: EditField, value: String) {
         match field {
             EditField::Name => self.form.name = value,
+            EditField::Description => self.form.description = value,
+            EditField::Tags => self.form.tags = value,
+            EditField::Group => self.form.group = value,
             EditField::CronExpression => self.form.cron_expression = value,
             EditField::Time => self.form.time = value,
-            EditField::Weekday => {
-                if let Ok(v) = value.parse::<u8>() {
-                    self.form.weekday = v;
+            EditField::Weekday => match scheduler::parse_weekday(&value) {
+                Ok(v) => self.form.weekday = v,
+                Err(err) => {
+                    self.input = None;
+                    self.message = format!("{err:#}");
+                    return;
                 }
-            }
-            EditField::Day => {
-                if let Ok(v) = value.parse::<u8>() {
-                    self.form.day = v;
+            },
+            EditField::Day => match scheduler::parse_day(&value) {
+                Ok(v) => self.form.day = v,
+                Err(err) => {
+                    self.input = None;
+                    self.message = format!("{err:#}");
+                    return;
                 }
-            }
+            },
+            EditField::Week => match scheduler::parse_week(&value) {
+                Ok(v) => self.form.week = v,
+                Err(err) => {
+                    self.input = None;
+                    self.message = format!("{err:#}");
+                    return;
+                }
+            },
+            EditField::Minute => match scheduler::parse_minute(&value) {
+                Ok(v) => self.form.minute = v,
+                Err(err) => {
+                    self.input = None;
+                    self.message = format!("{err:#}");
+                    return;
+                }
+            },
+            EditField::Month => match scheduler::parse_month(&value) {
+                Ok(v) => self.form.month = v,
+                Err(err) => {
+                    self.input = None;
+                    self.message = format!("{err:#}");
+                    return;
+                }
+            },
             EditField::OnceAt => self.form.once_at = value,
+            EditField::Every => self.form.every_seconds = value,
+            EditField::Delay => self.form.delay_seconds = value,
             EditField::Program => self.form.program = value,
             EditField::Args => self.form.args = value,
             EditField::WorkingDir => self.form.working_dir = value,
-            EditField::EnvJson => self.form.env_json = value,
+            EditField::EnvJson => match parse_env_input(&value) {
+                Ok(env) => self.form.env_json = env_json_string(&env),
+                Err(err) => {
+                    // Keep the typed text so it can be fixed in place; save refuses it until then.
+                    self.form.env_json = value;
+                    self.input = None;
+                    self.dirty = true;
+                    self.message = format!("{err:#}");
+                    return;
+                }
+            },
             EditField::Timeout => self.form.timeout_seconds = value,
             EditField::Repeat => {
                 self.form.repeat = parse_repeat(&value);
             }
-            EditField::Enabled | EditField::ScheduleKind => {}
+            EditField::Enabled | EditField::ManualOnly | EditField::ScheduleKind => {}
         }
         self.input = None;
         self.dirty = true;
-        self.message = "Field updated".to_string();
+        self.message = if field == EditField::CronExpression {
+            cron_feedback(&self.form.cron_expression)
+        } else {
+            "Field updated".to_string()
+        };
     }
 
     fn field_value(&self, field: EditField) -> String {
         match field {
             EditField::Name => self.form.name.clone(),
+            EditField::Description => self.form.description.clone(),
+            EditField::Tags => self.form.tags.clone(),
+            EditField::Group => self.form.group.clone(),
             EditField::Enabled => self.form.enabled.to_string(),
+            EditField::ManualOnly => self.form.manual_only.to_string(),
             EditField::ScheduleKind => match self.form.schedule_kind {
                 ScheduleKind::Cron => "cron".to_string(),
                 ScheduleKind::Simple => "simple".to_string(),
+                ScheduleKind::Interval => "interval".to_string(),
             },
             EditField::CronExpression => self.form.cron_expression.clone(),
             EditField::Repeat => repeat_label(&self.form.repeat).to_string(),
             EditField::Time => self.form.time.clone(),
-            EditField::Weekday => self.form.weekday.to_string(),
-            EditField::Day => self.form.day.to_string(),
+            EditField::Weekday => scheduler::weekday_name(self.form.weekday),
+            EditField::Day => scheduler::day_label(self.form.day),
+            EditField::Week => scheduler::week_label(self.form.week),
+            EditField::Minute => self.form.minute.to_string(),
+            EditField::Month => self.form.month.to_string(),
             EditField::OnceAt => self.form.once_at.clone(),
+            EditField::Every => self.form.every_seconds.clone(),
+            EditField::Delay => self.form.delay_seconds.clone(),
             EditField::Program => self.form.program.clone(),
             EditField::Args => self.form.args.clone(),
             EditField::WorkingDir => self.form.working_dir.clone(),
@@ -766,20 +1393,40 @@ impl EditState {
             .trim()
             .parse()
             .context("timeout_seconds must be number")?;
-        let env: HashMap<String, String> = if self.form.env_json.trim().is_empty() {
-            HashMap::new()
-        } else {
-            serde_json::from_str(&self.form.env_json).context("env_json must be JSON object")?
-        };
+        let env = parse_env_input(&self.form.env_json)?;
 
         let schedule = match self.form.schedule_kind {
             ScheduleKind::Cron => ScheduleConfig::Cron {
                 expression: self.form.cron_expression.trim().to_string(),
             },
+            ScheduleKind::Interval => ScheduleConfig::Interval {
+                every_seconds: self.form.every_seconds.trim().parse().context("every_seconds must be number")?,
+                delay_seconds: if self.form.delay_seconds.trim().is_empty() {
+                    0
+                } else {
+                    self.form.delay_seconds.trim().parse().context("delay_seconds must be number")?
+                },
+            },
             ScheduleKind::Simple => {
                 let repeat = self.form.repeat.clone();
+                let mut times = Vec::new();
                 let (time, weekday, day, once_at) = match repeat {
-                    Repeat::Daily => (Some(self.form.time.trim().to_string()), None, None, None),
+                    Repeat::Daily => {
+                        let mut list: Vec<String> = self
+                            .form
+                            .time
+                            .split(',')
+                            .map(|t| t.trim().to_string())
+                            .filter(|t| !t.is_empty())
+                            .collect();
+                        let time = if list.len() > 1 {
+                            times = list;
+                            None
+                        } else {
+                            list.pop()
+                        };
+                        (time, None, None, None)
+                    }
                     Repeat::Weekly => (
                         Some(self.form.time.trim().to_string()),
                         Some(self.form.weekday),
@@ -792,15 +1439,32 @@ impl EditState {
                         Some(self.form.day),
                         None,
                     ),
-                    Repeat::EveryMinute => (None, None, None, None),
+                    Repeat::MonthlyWeekday => (
+                        Some(self.form.time.trim().to_string()),
+                        Some(self.form.weekday),
+                        None,
+                        None,
+                    ),
+                    Repeat::EveryMinute | Repeat::Hourly => (None, None, None, None),
+                    Repeat::Yearly => (
+                        Some(self.form.time.trim().to_string()),
+                        None,
+                        Some(self.form.day),
+                        None,
+                    ),
                     Repeat::Once => (None, None, None, Some(self.form.once_at.trim().to_string())),
                 };
                 ScheduleConfig::Simple {
+                    minute: matches!(repeat, Repeat::Hourly).then_some(self.form.minute),
+                    month: matches!(repeat, Repeat::Yearly).then_some(self.form.month),
+                    week: matches!(repeat, Repeat::MonthlyWeekday).then_some(self.form.week),
                     repeat,
                     time,
+                    times,
                     weekday,
                     day,
                     once_at,
+                    dst_gap: self.form.dst_gap,
                 }
             }
         };
@@ -808,22 +1472,56 @@ impl EditState {
         let job = JobConfig {
             id: self.form.id.clone(),
             name: self.form.name.trim().to_string(),
+            description: Some(self.form.description.trim().to_string()).filter(|d| !d.is_empty()),
+            tags: self
+                .form
+                .tags
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            group: Some(self.form.group.trim().to_string()).filter(|g| !g.is_empty()),
             enabled: self.form.enabled,
+            manual_only: self.form.manual_only,
+            allow_overlap: self.form.allow_overlap,
+            log_successes: self.form.log_successes,
+            delete_after_run: self.form.delete_after_run,
             schedule,
             command: CommandConfig {
                 program: self.form.program.trim().to_string(),
-                args: split_args(&self.form.args),
+                args: split_args(&self.form.args)?,
                 working_dir: if self.form.working_dir.trim().is_empty() {
                     None
                 } else {
                     Some(self.form.working_dir.trim().to_string())
                 },
                 env,
+                ..self.form.base_command.clone()
             },
             timeout_seconds,
+            warn_after_seconds: self.form.warn_after_seconds,
+            slow_anomaly_factor: self.form.slow_anomaly_factor,
+            max_manual_runs: self.form.max_manual_runs,
+            disable_after_failures: self.form.disable_after_failures,
+            active_from: self.form.active_from.clone(),
+            active_until: self.form.active_until.clone(),
+            output_tail_lines: self.form.output_tail_lines,
+            on_success: self.form.on_success.clone(),
+            on_failure: self.form.on_failure.clone(),
+            on_slow: self.form.on_slow.clone(),
+            ping_url_on_success: self.form.ping_url_on_success.clone(),
+            ping_url_on_start: self.form.ping_url_on_start.clone(),
+            created_at: self.form.created_at,
+            updated_at: None,
+            // Re-enabling a completed one-shot makes it schedulable again.
+            completed_at: self.form.completed_at.filter(|_| !self.form.enabled),
+            extra: self.form.extra.clone(),
         };
 
         validate_candidate(&job)?;
+        if let Some(warning) = config::schedule_warnings(&job, Local::now()).into_iter().next() {
+            bail!("{warning} (disable the job to save it anyway)");
+        }
         Ok(job)
     }
 }
@@ -841,24 +1539,66 @@ impl Clone for EditState {
 }
 
 impl JobForm {
-    fn new(id: String) -> Self {
-        Self {
+    fn new(id: String, defaults: Option<&serde_json::Value>) -> Self {
+        let mut form = Self {
             id,
             name: String::new(),
+            description: String::new(),
+            tags: String::new(),
+            group: String::new(),
             enabled: false,
+            manual_only: false,
             schedule_kind: ScheduleKind::Simple,
             cron_expression: "0 2 * * *".to_string(),
             repeat: Repeat::Daily,
             time: "09:00".to_string(),
             weekday: 1,
             day: 1,
+            week: 1,
+            minute: 0,
+            month: 1,
             once_at: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            every_seconds: "3600".to_string(),
+            delay_seconds: "0".to_string(),
             program: String::new(),
             args: String::new(),
             working_dir: String::new(),
             env_json: "{}".to_string(),
             timeout_seconds: "3600".to_string(),
+            base_command: CommandConfig::default(),
+            on_success: None,
+            on_failure: None,
+            on_slow: None,
+            ping_url_on_success: None,
+            ping_url_on_start: None,
+            warn_after_seconds: None,
+            slow_anomaly_factor: None,
+            dst_gap: DstGap::default(),
+            max_manual_runs: None,
+            disable_after_failures: None,
+            active_from: None,
+            active_until: None,
+            allow_overlap: true,
+            log_successes: true,
+            delete_after_run: false,
+            output_tail_lines: None,
+            created_at: None,
+            completed_at: None,
+            extra: serde_json::Map::new(),
+        };
+        if let Some(defaults) = defaults {
+            if let Some(timeout) = defaults.get("timeout_seconds").and_then(|v| v.as_u64()) {
+                form.timeout_seconds = timeout.to_string();
+            }
+            let command = defaults.get("command");
+            if let Some(dir) = command.and_then(|c| c.get("working_dir")).and_then(|v| v.as_str()) {
+                form.working_dir = dir.to_string();
+            }
+            if let Some(env) = command.and_then(|c| c.get("env")).filter(|v| v.is_object()) {
+                form.env_json = env.to_string();
+            }
         }
+        form
     }
 
     fn from_job(job: &JobConfig) -> Self {
@@ -875,38 +1615,100 @@ impl JobForm {
             ScheduleConfig::Simple {
                 repeat,
                 time,
+                times,
                 weekday,
                 day,
                 once_at,
+                ..
             } => (
                 ScheduleKind::Simple,
                 "0 2 * * *".to_string(),
                 repeat.clone(),
-                time.clone().unwrap_or_else(|| "09:00".to_string()),
+                if times.is_empty() {
+                    time.clone().unwrap_or_else(|| "09:00".to_string())
+                } else {
+                    time.iter().chain(times.iter()).cloned().collect::<Vec<_>>().join(",")
+                },
                 weekday.unwrap_or(1),
                 day.unwrap_or(1),
                 once_at
                     .clone()
                     .unwrap_or_else(|| Local::now().format("%Y-%m-%d %H:%M").to_string()),
             ),
+            ScheduleConfig::Interval { .. } => (
+                ScheduleKind::Interval,
+                "0 2 * * *".to_string(),
+                Repeat::Daily,
+                "09:00".to_string(),
+                1,
+                1,
+                Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            ),
+        };
+        let (every_seconds, delay_seconds) = match &job.schedule {
+            ScheduleConfig::Interval {
+                every_seconds,
+                delay_seconds,
+            } => (every_seconds.to_string(), delay_seconds.to_string()),
+            _ => ("3600".to_string(), "0".to_string()),
+        };
+
+        let (minute, month, week, dst_gap) = match &job.schedule {
+            ScheduleConfig::Simple {
+                minute,
+                month,
+                week,
+                dst_gap,
+                ..
+            } => (minute.unwrap_or(0), month.unwrap_or(1), week.unwrap_or(1), *dst_gap),
+            ScheduleConfig::Cron { .. } | ScheduleConfig::Interval { .. } => (0, 1, 1, DstGap::default()),
         };
 
         Self {
             id: job.id.clone(),
             name: job.name.clone(),
+            description: job.description.clone().unwrap_or_default(),
+            tags: job.tags.join(","),
+            group: job.group.clone().unwrap_or_default(),
             enabled: job.enabled,
+            manual_only: job.manual_only,
             schedule_kind,
             cron_expression,
             repeat,
             time,
             weekday,
             day,
+            week,
+            minute,
+            month,
             once_at,
+            every_seconds,
+            delay_seconds,
             program: job.command.program.clone(),
-            args: job.command.args.join(" "),
+            args: join_args(&job.command.args),
             working_dir: job.command.working_dir.clone().unwrap_or_default(),
             env_json: serde_json::to_string(&job.command.env).unwrap_or_else(|_| "{}".to_string()),
             timeout_seconds: job.timeout_seconds.to_string(),
+            base_command: job.command.clone(),
+            on_success: job.on_success.clone(),
+            on_failure: job.on_failure.clone(),
+            on_slow: job.on_slow.clone(),
+            ping_url_on_success: job.ping_url_on_success.clone(),
+            ping_url_on_start: job.ping_url_on_start.clone(),
+            warn_after_seconds: job.warn_after_seconds,
+            slow_anomaly_factor: job.slow_anomaly_factor,
+            dst_gap,
+            max_manual_runs: job.max_manual_runs,
+            disable_after_failures: job.disable_after_failures,
+            active_from: job.active_from.clone(),
+            active_until: job.active_until.clone(),
+            allow_overlap: job.allow_overlap,
+            log_successes: job.log_successes,
+            delete_after_run: job.delete_after_run,
+            output_tail_lines: job.output_tail_lines,
+            created_at: job.created_at,
+            completed_at: job.completed_at,
+            extra: job.extra.clone(),
         }
     }
 }
@@ -914,10 +1716,11 @@ impl JobForm {
 fn render(frame: &mut Frame<'_>, ui: &UiState) {
     let root = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Min(8), Constraint::Length(4)])
+        .constraints([Constraint::Length(1), Constraint::Min(8), Constraint::Length(5)])
         .split(frame.area());
 
     let daemon_text = match ui.daemon_pid {
+        Some(pid) if ui.daemon_paused => format!("daemon: paused(pid={pid})"),
         Some(pid) => format!("daemon: running(pid={pid})"),
         None => "daemon: stopped".to_string(),
     };
@@ -926,12 +1729,24 @@ fn render(frame: &mut Frame<'_>, ui: &UiState) {
         UiMode::Edit(_) => format!("Macrond TUI - Edit Job | {daemon_text}"),
         UiMode::ConfirmDelete { .. } => format!("Macrond TUI - Confirm Delete | {daemon_text}"),
         UiMode::ConfirmDiscard { .. } => format!("Macrond TUI - Confirm Discard | {daemon_text}"),
+        UiMode::ConfirmTest { .. } => format!("Macrond TUI - Confirm Test | {daemon_text}"),
+        UiMode::ConfirmEnable { .. } => format!("Macrond TUI - Confirm Enable | {daemon_text}"),
+        UiMode::TestOutput(_) => format!("Macrond TUI - Test Job | {daemon_text}"),
+        UiMode::ViewJson { .. } => format!("Macrond TUI - Job JSON | {daemon_text}"),
+    };
+    let title = if ui.read_only { format!("{title} | read-only") } else { title };
+    let title = match &ui.skipped_files {
+        Some(skipped_files) => Line::from(vec![
+            Span::raw(title),
+            Span::styled(format!(" | {skipped_files}"), ui.theme.fg(Color::Red)),
+        ]),
+        None => Line::from(title),
     };
     frame.render_widget(Paragraph::new(title), root[0]);
 
     match &ui.mode {
         UiMode::List => render_list(frame, root[1], ui),
-        UiMode::Edit(edit) => render_edit(frame, root[1], edit),
+        UiMode::Edit(edit) => render_edit(frame, root[1], edit, ui.theme),
         UiMode::ConfirmDelete { job_id } => {
             let p = Paragraph::new(format!("Delete job '{job_id}' ?\nPress y to confirm, n/Esc to cancel."))
                 .block(Block::default().title("Confirm").borders(Borders::ALL));
@@ -942,25 +1757,63 @@ fn render(frame: &mut Frame<'_>, ui: &UiState) {
                 .block(Block::default().title("Confirm").borders(Borders::ALL));
             frame.render_widget(p, root[1]);
         }
+        UiMode::ConfirmTest { job_id } => {
+            let p = Paragraph::new(format!(
+                "Run job '{job_id}' now as a test?\nThe command really executes. Press y to run, n/Esc to cancel."
+            ))
+            .block(Block::default().title("Confirm").borders(Borders::ALL));
+            frame.render_widget(p, root[1]);
+        }
+        UiMode::ConfirmEnable { job_id, reason } => {
+            let p = Paragraph::new(format!(
+                "Job '{job_id}' has no future runs ({reason}).\nEnable anyway? Press y to enable, n/Esc to keep it disabled."
+            ))
+            .block(Block::default().title("Confirm").borders(Borders::ALL));
+            frame.render_widget(p, root[1]);
+        }
+        UiMode::TestOutput(run) => render_test_output(frame, root[1], run, ui.theme),
+        UiMode::ViewJson { job_id, lines, scroll } => {
+            let p = Paragraph::new(lines.join("\n"))
+                .block(
+                    Block::default()
+                        .title(format!("Job JSON: {job_id} (read-only)"))
+                        .borders(Borders::ALL)
+                        .border_style(ui.theme.border()),
+                )
+                .scroll(((*scroll).min(u16::MAX as usize) as u16, 0));
+            frame.render_widget(p, root[1]);
+        }
     }
 
     let help = match &ui.mode {
+        UiMode::List if ui.read_only => {
+            "h/Left:focus jobs  l/Right:focus history  j/k:move  Enter:expand/collapse group  v:view JSON  r:refresh  q:quit  (read-only)\nHistory focus: Enter shows selected full line in Status."
+        }
         UiMode::List => {
-            "h/Left:focus jobs  l/Right:focus history  j/k:move  a:add  e/Enter:edit  d:delete  s:toggle job  t:test job  S:start daemon  X:stop daemon  r:refresh  q:quit\nHistory focus: Enter shows selected full line in Status."
+            "h/Left:focus jobs  l/Right:focus history  j/k:move  a:add  e/Enter:edit  d:delete  s:toggle job/group  u:undo  Enter:expand/collapse group  t:test job  R:run now  v:view JSON  S:start daemon  X:stop daemon  r:refresh  q:quit\nHistory focus: Enter shows selected full line in Status."
         }
         UiMode::Edit(edit) => {
             if edit.input.is_some() {
                 "Input mode: type text  Ctrl+C:clear  Enter:apply  Backspace:delete  Esc:cancel\nEditor: j/k:move field  s:save  q/Esc:back"
             } else {
-                "Editor: j/k:move field  Enter:edit/toggle  s:save  q/Esc:back\nRepeat options: daily/weekly/monthly/everyminute/once"
+                "Editor: j/k:move field  Enter:edit/toggle  s:save  q/Esc:back\nRepeat options: daily/weekly/monthly/everyminute/hourly/yearly/once"
             }
         }
-        UiMode::ConfirmDelete { .. } | UiMode::ConfirmDiscard { .. } => {
+        UiMode::ConfirmDelete { .. }
+        | UiMode::ConfirmDiscard { .. }
+        | UiMode::ConfirmTest { .. }
+        | UiMode::ConfirmEnable { .. } => {
             "Confirm mode: y:yes  n:no  Esc:cancel\n"
         }
+        UiMode::TestOutput(_) => "Test output: j/k:scroll  g:top  G:follow  q/Esc:close (kills if still running)\n",
+        UiMode::ViewJson { .. } => "Job JSON: j/k:scroll  g/G:top/bottom  y:copy to clipboard  q/Esc:close\n",
     };
 
-    let footer = Paragraph::new(format!("{}\nStatus: {}", help, ui.message))
+    let status = match &ui.mode {
+        UiMode::Edit(edit) => &edit.message,
+        _ => &ui.message,
+    };
+    let footer = Paragraph::new(format!("{}\nStatus: {}", help, status))
         .block(Block::default().title("Help").borders(Borders::ALL));
     frame.render_widget(footer, root[2]);
 }
@@ -975,17 +1828,67 @@ fn render_list(frame: &mut Frame<'_>, area: ratatui::layout::Rect, ui: &UiState)
     let job_items: Vec<ListItem<'_>> = if ui.jobs.is_empty() {
         vec![ListItem::new("No jobs. Press 'a' to create one.")]
     } else {
-        ui.jobs
-            .iter()
-            .map(|job| {
+        ui.rows()
+            .into_iter()
+            .map(|row| {
+                let job = match row {
+                    ListRow::Job(idx) => &ui.jobs[idx],
+                    ListRow::Group(group) => {
+                        let members: Vec<&JobConfig> =
+                            ui.jobs.iter().filter(|j| j.group.as_ref() == Some(&group)).collect();
+                        return ListItem::new(Line::styled(
+                            format!(
+                                "[{}] group {} ({} jobs, {} on)",
+                                if ui.collapsed_groups.contains(&group) { "+" } else { "-" },
+                                group,
+                                members.len(),
+                                members.iter().filter(|j| j.enabled).count()
+                            ),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ));
+                    }
+                };
+                let indent = if job.group.is_some() { "  " } else { "" };
                 let schedule = scheduler::schedule_label(job);
-                ListItem::new(format!(
-                    "[{}] {} ({}) {}",
+                let last = ui.last_status.as_ref().map(|m| m.get(&job.id).map(String::as_str));
+                let (color, marker) = match last {
+                    Some(Some("success")) => (Color::Green, " ok"),
+                    Some(Some(_)) => (Color::Red, " !!"),
+                    Some(None) => (Color::Yellow, " --"),
+                    None => (Color::Reset, ""),
+                };
+                let next = ui
+                    .next_runs
+                    .get(&job.id)
+                    .map(|t| format!("  next {}", t.format("%Y-%m-%d %H:%M")))
+                    .unwrap_or_default();
+                let mut spans = vec![Span::raw(format!(
+                    "{indent}[{}]{} {} ({}) {}{}",
                     if job.enabled { "on" } else { "  " },
+                    if ui.theme.plain { marker } else { "" },
                     job.id,
                     job.name,
-                    schedule
-                ))
+                    schedule,
+                    next
+                ))];
+                if let Some(outcomes) = ui.outcomes.get(&job.id) {
+                    spans.push(Span::raw("  "));
+                    spans.extend(outcomes.iter().map(|status| match status.as_str() {
+                        "success" => Span::styled("✓", ui.theme.fg(Color::Green)),
+                        "timeout" => Span::styled("✗", ui.theme.fg(Color::Yellow)),
+                        _ => Span::styled("✗", ui.theme.fg(Color::Red)),
+                    }));
+                }
+                let mut lines = vec![Line::from(spans)];
+                let updated = job.updated_at.map(|t| format!("updated {}", t.format("%Y-%m-%d %H:%M")));
+                let detail: Vec<String> = job.description.iter().cloned().chain(updated).collect();
+                if !detail.is_empty() {
+                    lines.push(Line::styled(
+                        format!("{indent}     {}", detail.join("  ")),
+                        Style::default().add_modifier(Modifier::DIM),
+                    ));
+                }
+                ListItem::new(lines).style(ui.theme.fg(color))
             })
             .collect()
     };
@@ -994,13 +1897,13 @@ fn render_list(frame: &mut Frame<'_>, area: ratatui::layout::Rect, ui: &UiState)
         Block::default()
             .title("Jobs (focused)")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
+            .border_style(ui.theme.border())
     } else {
         Block::default().title("Jobs").borders(Borders::ALL)
     };
     let jobs = List::new(job_items)
         .block(jobs_block)
-        .highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
+        .highlight_style(ui.theme.highlight())
         .highlight_symbol(" > ");
     frame.render_stateful_widget(jobs, body[0], &mut state);
 
@@ -1015,7 +1918,7 @@ fn render_list(frame: &mut Frame<'_>, area: ratatui::layout::Rect, ui: &UiState)
     } else {
         ui.history_runs
             .iter()
-            .take(100)
+            .take(daemon::history_size())
             .map(|line| ListItem::new(line.clone()))
             .collect()
     };
@@ -1023,28 +1926,55 @@ fn render_list(frame: &mut Frame<'_>, area: ratatui::layout::Rect, ui: &UiState)
         Block::default()
             .title("History Runs (focused)")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
+            .border_style(ui.theme.border())
     } else {
         Block::default().title("History Runs").borders(Borders::ALL)
     };
     let runs = List::new(run_items)
         .block(history_block)
-        .highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White))
+        .highlight_style(ui.theme.selection())
         .highlight_symbol(" > ");
     frame.render_stateful_widget(runs, right[0], &mut history_state);
 
-    let detail = ui
-        .history_runs
-        .get(ui.history_selected)
-        .cloned()
-        .unwrap_or_else(|| "No history line selected".to_string());
+    let detail = match ui.history_runs.get(ui.history_selected) {
+        Some(line) => {
+            let tail = line
+                .split_whitespace()
+                .find_map(|field| field.strip_prefix("run_id="))
+                .and_then(|run_id| ui.output_tails.get(run_id));
+            match tail {
+                Some(tail) => format!("{line}\n\noutput (last {} lines):\n{}", tail.len(), tail.join("\n")),
+                None => line.clone(),
+            }
+        }
+        None => "No history line selected".to_string(),
+    };
     let detail_widget = Paragraph::new(detail)
         .block(Block::default().title("History Detail").borders(Borders::ALL))
         .wrap(ratatui::widgets::Wrap { trim: false });
     frame.render_widget(detail_widget, right[1]);
 }
 
-fn render_edit(frame: &mut Frame<'_>, area: ratatui::layout::Rect, edit: &EditState) {
+fn render_test_output(frame: &mut Frame<'_>, area: ratatui::layout::Rect, run: &TestRun, theme: Theme) {
+    let state = run.exit.as_deref().unwrap_or("running");
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = if run.follow {
+        run.lines.len().saturating_sub(visible)
+    } else {
+        run.scroll
+    };
+    let widget = Paragraph::new(run.lines.join("\n"))
+        .block(
+            Block::default()
+                .title(format!("Test output: {} ({state})", run.job_id))
+                .borders(Borders::ALL)
+                .border_style(theme.border()),
+        )
+        .scroll((scroll.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(widget, area);
+}
+
+fn render_edit(frame: &mut Frame<'_>, area: ratatui::layout::Rect, edit: &EditState, theme: Theme) {
     let inner_width = area.width.saturating_sub(2);
     let content_width = inner_width.saturating_sub(3);
     let wrap_width = content_width.max(1) as usize;
@@ -1062,7 +1992,12 @@ fn render_edit(frame: &mut Frame<'_>, area: ratatui::layout::Rect, edit: &EditSt
     for field in fields {
         let label = field_label(field);
         let value = edit.field_value(field);
-        items.push(ListItem::new(wrap_field_text(label, &value, wrap_width)));
+        let item = ListItem::new(wrap_field_text(label, &value, wrap_width));
+        if field == EditField::EnvJson && parse_env_input(&value).is_err() {
+            items.push(item.style(theme.fg(Color::Red).add_modifier(Modifier::BOLD)));
+        } else {
+            items.push(item);
+        }
     }
 
     let editor = List::new(items)
@@ -1070,9 +2005,9 @@ fn render_edit(frame: &mut Frame<'_>, area: ratatui::layout::Rect, edit: &EditSt
             Block::default()
                 .title("Job Editor")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(theme.border()),
         )
-        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+        .highlight_style(theme.selection().add_modifier(Modifier::BOLD))
         .highlight_symbol(" > ");
 
     frame.render_stateful_widget(editor, area, &mut state);
@@ -1103,7 +2038,7 @@ fn render_edit(frame: &mut Frame<'_>, area: ratatui::layout::Rect, edit: &EditSt
                 }
 
                 if let Some(state) = suggest {
-                    render_suggest_list(frame, area, popup, state);
+                    render_suggest_list(frame, area, popup, state, theme);
                 }
             }
             InputKind::Select { options, selected } => {
@@ -1133,7 +2068,7 @@ fn wrap_field_text(label: &str, value: &str, width: usize) -> Text<'static> {
 
     let first_width = width.saturating_sub(prefix.len()).max(1);
     let rest_width = width.saturating_sub(indent.len()).max(1);
-    let (first, rest) = split_at_chars(value, first_width);
+    let (first, rest) = split_at_width(value, first_width);
     let mut lines = Vec::new();
     lines.push(Line::from(format!("{prefix}{first}")));
     for chunk in split_chunks(&rest, rest_width) {
@@ -1154,24 +2089,29 @@ fn wrap_input_text(label: &str, value: &str, cursor: usize, width: usize) -> (Te
     let first_width = width.saturating_sub(prefix.len()).max(1);
     let rest_width = width.saturating_sub(indent.len()).max(1);
 
-    let cursor_chars = value.get(0..cursor).unwrap_or(value).chars().count();
-    let (first, rest) = split_at_chars(value, first_width);
+    let cursor = cursor.min(value.len());
+    let (first, rest) = split_at_width(value, first_width);
+    let chunks = split_chunks(&rest, rest_width);
     let mut lines = Vec::new();
     lines.push(Line::from(title));
     lines.push(Line::from(hint));
     lines.push(Line::from(format!("{prefix}{first}")));
-    for chunk in split_chunks(&rest, rest_width) {
+    for chunk in &chunks {
         lines.push(Line::from(format!("{indent}{chunk}")));
     }
 
-    let (cursor_line, cursor_col) = if cursor_chars <= first_width {
-        (2, prefix.len() + cursor_chars)
-    } else {
-        let remaining = cursor_chars - first_width;
-        let line_offset = remaining / rest_width + 1;
-        let col = indent.len() + (remaining % rest_width);
-        (2 + line_offset, col)
-    };
+    let (mut cursor_line, mut cursor_col) = (2, prefix.len() + value.get(..cursor).unwrap_or(value).width());
+    if cursor > first.len() {
+        let mut offset = cursor - first.len();
+        for (idx, chunk) in chunks.iter().enumerate() {
+            if offset <= chunk.len() {
+                cursor_line = 3 + idx;
+                cursor_col = indent.len() + chunk.get(..offset).unwrap_or(chunk).width();
+                break;
+            }
+            offset -= chunk.len();
+        }
+    }
 
     let cursor_pos = Some((cursor_col as u16, cursor_line as u16));
     (Text::from(lines), cursor_pos)
@@ -1348,12 +2288,12 @@ fn list_files_recursive(
         let path = entry.path();
         if path.is_dir() {
             list_files_recursive(root, &path, out, count, limit);
-        } else if path.is_file() {
-            if let Ok(rel) = path.strip_prefix(root) {
-                let rel = rel.to_string_lossy().replace('\\', "/");
-                out.push(rel);
-                *count += 1;
-            }
+        } else if path.is_file()
+            && let Ok(rel) = path.strip_prefix(root)
+        {
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            out.push(rel);
+            *count += 1;
         }
     }
 }
@@ -1391,6 +2331,7 @@ fn render_suggest_list(
     area: ratatui::layout::Rect,
     popup: ratatui::layout::Rect,
     state: &SuggestState,
+    theme: Theme,
 ) {
     if state.options.is_empty() {
         return;
@@ -1404,7 +2345,7 @@ fn render_suggest_list(
     let mut list_state = ListState::default().with_selected(Some(selected));
     let widget = List::new(items)
         .block(Block::default().title("Dirs").borders(Borders::ALL))
-        .highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White))
+        .highlight_style(theme.selection())
         .highlight_symbol(" > ");
     frame.render_stateful_widget(widget, rect, &mut list_state);
 }
@@ -1453,11 +2394,16 @@ fn dropdown_rect(
     rect
 }
 
-fn split_at_chars(s: &str, count: usize) -> (String, String) {
-    let mut iter = s.chars();
-    let head: String = iter.by_ref().take(count).collect();
-    let tail: String = iter.collect();
-    (head, tail)
+fn split_at_width(s: &str, width: usize) -> (String, String) {
+    let mut used = 0;
+    for (idx, ch) in s.char_indices() {
+        let w = ch.width().unwrap_or(0);
+        if used + w > width && idx > 0 {
+            return (s[..idx].to_string(), s[idx..].to_string());
+        }
+        used += w;
+    }
+    (s.to_string(), String::new())
 }
 
 fn split_chunks(s: &str, width: usize) -> Vec<String> {
@@ -1466,12 +2412,15 @@ fn split_chunks(s: &str, width: usize) -> Vec<String> {
     }
     let mut chunks = Vec::new();
     let mut current = String::new();
+    let mut used = 0;
     for ch in s.chars() {
-        if current.chars().count() >= width {
-            chunks.push(current);
-            current = String::new();
+        let w = ch.width().unwrap_or(0);
+        if used + w > width && !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+            used = 0;
         }
         current.push(ch);
+        used += w;
     }
     if !current.is_empty() {
         chunks.push(current);
@@ -1482,19 +2431,28 @@ fn split_chunks(s: &str, width: usize) -> Vec<String> {
 fn field_label(field: EditField) -> &'static str {
     match field {
         EditField::Name => "name",
+        EditField::Description => "description",
+        EditField::Tags => "tags (comma separated)",
+        EditField::Group => "group (optional)",
         EditField::Enabled => "enabled (Enter toggle)",
+        EditField::ManualOnly => "manual_only (Enter toggle, never scheduled)",
         EditField::ScheduleKind => "schedule_type (Enter toggle)",
         EditField::CronExpression => "cron_expression",
         EditField::Repeat => "repeat",
-        EditField::Time => "time (HH:MM)",
-        EditField::Weekday => "weekday (1-7)",
-        EditField::Day => "day (1-31)",
-        EditField::OnceAt => "once_at (YYYY-MM-DD HH:MM)",
+        EditField::Time => "time (HH:MM, daily allows HH:MM,HH:MM)",
+        EditField::Weekday => "weekday (mon-sun or 1-7)",
+        EditField::Day => "day (1-31, last)",
+        EditField::Week => "week (1-4, last)",
+        EditField::Minute => "minute (0-59)",
+        EditField::Month => "month (1-12)",
+        EditField::OnceAt => "once_at (YYYY-MM-DD HH:MM or RFC3339)",
+        EditField::Every => "every_seconds (interval from daemon start)",
+        EditField::Delay => "delay_seconds (first run after daemon start)",
         EditField::Program => "program",
         EditField::Args => "args",
         EditField::WorkingDir => "working_dir",
-        EditField::EnvJson => "env_json",
-        EditField::Timeout => "timeout_seconds",
+        EditField::EnvJson => "env_json (JSON object or KEY=VALUE ...)",
+        EditField::Timeout => "timeout_seconds (0 = no timeout)",
     }
 }
 
@@ -1503,7 +2461,10 @@ fn repeat_label(repeat: &Repeat) -> &'static str {
         Repeat::Daily => "daily",
         Repeat::Weekly => "weekly",
         Repeat::Monthly => "monthly",
+        Repeat::MonthlyWeekday => "monthlyweekday",
         Repeat::EveryMinute => "everyminute",
+        Repeat::Hourly => "hourly",
+        Repeat::Yearly => "yearly",
         Repeat::Once => "once",
     }
 }
@@ -1512,18 +2473,49 @@ fn parse_repeat(s: &str) -> Repeat {
     match s {
         "weekly" => Repeat::Weekly,
         "monthly" => Repeat::Monthly,
+        "monthlyweekday" => Repeat::MonthlyWeekday,
         "everyminute" => Repeat::EveryMinute,
+        "hourly" => Repeat::Hourly,
+        "yearly" => Repeat::Yearly,
         "once" => Repeat::Once,
         _ => Repeat::Daily,
     }
 }
 
-fn split_args(s: &str) -> Vec<String> {
+fn split_args(s: &str) -> Result<Vec<String>> {
     if s.trim().is_empty() {
-        Vec::new()
+        Ok(Vec::new())
     } else {
-        s.split_whitespace().map(|v| v.to_string()).collect()
+        shlex::split(s).context("args has unbalanced quotes or a trailing escape")
+    }
+}
+
+// Accepts a JSON object or, easier to type on one line, `KEY=VALUE` pairs split like args.
+fn parse_env_input(value: &str) -> Result<HashMap<String, String>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(HashMap::new());
     }
+    if value.starts_with('{') {
+        return serde_json::from_str(value).map_err(|err| anyhow::anyhow!("invalid JSON: {err}"));
+    }
+    let words = shlex::split(value).context("invalid env: unbalanced quotes or a trailing escape")?;
+    words
+        .into_iter()
+        .map(|word| match word.split_once('=') {
+            Some((key, val)) if !key.is_empty() => Ok((key.to_string(), val.to_string())),
+            _ => bail!("invalid env: '{word}' is not KEY=VALUE (or use a JSON object)"),
+        })
+        .collect()
+}
+
+fn env_json_string(env: &HashMap<String, String>) -> String {
+    let sorted: std::collections::BTreeMap<_, _> = env.iter().collect();
+    serde_json::to_string(&sorted).unwrap_or_else(|_| "{}".to_string())
+}
+
+fn join_args(args: &[String]) -> String {
+    shlex::try_join(args.iter().map(|a| a.as_str())).unwrap_or_else(|_| args.join(" "))
 }
 
 fn centered_rect(percent_x: u16, height: u16, area: ratatui::layout::Rect) -> ratatui::layout::Rect {
@@ -1556,13 +2548,16 @@ fn generate_job_id() -> String {
 }
 
 fn write_job(paths: &AppPaths, job: &JobConfig) -> Result<()> {
-    let path = job_file_path(&paths.jobs_dir, &job.id);
-    fs::write(path, serde_json::to_vec_pretty(job)?)?;
-    Ok(())
+    let path = config::job_file_path(&paths.jobs_dir, &job.id);
+    let now = Local::now();
+    let mut job = job.clone();
+    job.created_at.get_or_insert(now);
+    job.updated_at = Some(now);
+    config::write_job_file(&path, &job)
 }
 
 fn load_job_by_id(jobs_dir: &Path, job_id: &str) -> Result<JobConfig> {
-    let path = job_file_path(jobs_dir, job_id);
+    let path = config::job_file_path(jobs_dir, job_id);
     if !path.exists() {
         bail!("job file not found: {}", path.display());
     }
@@ -1570,33 +2565,71 @@ fn load_job_by_id(jobs_dir: &Path, job_id: &str) -> Result<JobConfig> {
     Ok(serde_json::from_str(&raw)?)
 }
 
-fn set_job_enabled(paths: &AppPaths, job_id: &str, enabled: bool) -> Result<()> {
-    let mut job = load_job_by_id(&paths.jobs_dir, job_id)?;
-    job.enabled = enabled;
-    write_job(paths, &job)?;
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    let mut child = StdCommand::new("pbcopy")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("pbcopy unavailable")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        std::io::Write::write_all(&mut stdin, text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("pbcopy exited with {status}");
+    }
     Ok(())
 }
 
-fn run_test(paths: &AppPaths, job_id: &str) -> Result<String> {
+fn start_test_run(paths: &AppPaths, job_id: &str) -> Result<TestRun> {
     let exe = std::env::current_exe()?;
-    let output = StdCommand::new(exe)
+    let mut child = StdCommand::new(exe)
         .arg("--base-dir")
         .arg(&paths.base_dir)
+        .arg("--history-size")
+        .arg(daemon::history_size().to_string())
+        .args(config::verify_checksums().then_some("--verify-jobs"))
+        .arg("--file-mode")
+        .arg(format!("{:04o}", paths::file_mode()))
+        .args(paths.config_args())
         .arg("run")
+        .arg("--inline")
+        .arg("--show-output")
         .arg(job_id)
-        .env("EZCRON_FORCE_INLINE", "1")
-        .output()?;
-    if output.status.success() {
-        let out = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if out.is_empty() {
-            Ok(format!("Test finished for {job_id}"))
-        } else {
-            Ok(format!("Test result: {out}"))
-        }
-    } else {
-        let err = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        Ok(format!("Test failed for {job_id}: {err}"))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()?;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        spawn_line_reader(stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_line_reader(stderr, tx);
     }
+
+    Ok(TestRun {
+        job_id: job_id.to_string(),
+        child,
+        rx,
+        lines: Vec::new(),
+        scroll: 0,
+        follow: true,
+        exit: None,
+    })
+}
+
+fn spawn_line_reader(stream: impl Read + Send + 'static, tx: Sender<String>) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(|l| l.ok()) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
 }
 
 fn daemon_command(paths: &AppPaths, cmd: &str) -> Result<String> {
@@ -1604,6 +2637,12 @@ fn daemon_command(paths: &AppPaths, cmd: &str) -> Result<String> {
     let output = StdCommand::new(exe)
         .arg("--base-dir")
         .arg(&paths.base_dir)
+        .arg("--history-size")
+        .arg(daemon::history_size().to_string())
+        .args(config::verify_checksums().then_some("--verify-jobs"))
+        .arg("--file-mode")
+        .arg(format!("{:04o}", paths::file_mode()))
+        .args(paths.config_args())
         .arg(cmd)
         .output()?;
     if output.status.success() {
@@ -1622,21 +2661,152 @@ fn daemon_command(paths: &AppPaths, cmd: &str) -> Result<String> {
 fn validate_candidate(job: &JobConfig) -> Result<()> {
     let raw = serde_json::to_string(job)?;
     let parsed: JobConfig = serde_json::from_str(&raw)?;
-    let dir = std::env::temp_dir().join(format!("macrond-validate-{}", std::process::id()));
-    if dir.exists() {
-        fs::remove_dir_all(&dir)?;
-    }
+    // Unique per call: several TUIs (one per base-dir) may validate at the same time.
+    let dir = std::env::temp_dir().join(format!("macrond-validate-{}-{}", std::process::id(), uuid::Uuid::new_v4()));
     fs::create_dir_all(&dir)?;
     let path = dir.join(format!("{}.json", parsed.id));
-    fs::write(&path, serde_json::to_vec_pretty(&parsed)?)?;
-    let _ = config::load_jobs(&dir)?;
-    fs::remove_file(path)?;
-    fs::remove_dir_all(dir)?;
-    Ok(())
+    let content = serde_json::to_vec_pretty(&parsed)?;
+    let result = paths::write_file(&path, content)
+        .map_err(anyhow::Error::from)
+        .and_then(|()| config::load_job_file(&path, None).map_err(anyhow::Error::from));
+    let _ = fs::remove_dir_all(&dir);
+    result.map(|_| ())
+}
+
+fn cron_feedback(expression: &str) -> String {
+    let expression = expression.trim();
+    if let Err(err) = cron::Schedule::from_str(expression) {
+        return format!("Invalid cron expression: {err}");
+    }
+
+    let job = JobConfig {
+        id: String::new(),
+        name: String::new(),
+        description: None,
+        tags: Vec::new(),
+        group: None,
+        enabled: true,
+        manual_only: false,
+        allow_overlap: true,
+        log_successes: true,
+        delete_after_run: false,
+        schedule: ScheduleConfig::Cron {
+            expression: expression.to_string(),
+        },
+        command: CommandConfig::default(),
+        timeout_seconds: 0,
+        warn_after_seconds: None,
+        slow_anomaly_factor: None,
+        max_manual_runs: None,
+        disable_after_failures: None,
+        active_from: None,
+        active_until: None,
+        output_tail_lines: None,
+        on_success: None,
+        on_failure: None,
+        on_slow: None,
+        ping_url_on_success: None,
+        ping_url_on_start: None,
+        created_at: None,
+        updated_at: None,
+        completed_at: None,
+        extra: serde_json::Map::new(),
+    };
+    let mut upcoming = Vec::new();
+    let mut after = Local::now();
+    while upcoming.len() < 3 {
+        match scheduler::next_run_after(&job, after) {
+            Ok(Some(next)) => {
+                upcoming.push(next.format("%Y-%m-%d %H:%M:%S").to_string());
+                after = next;
+            }
+            Ok(None) => break,
+            Err(err) => return format!("Invalid cron expression: {err:#}"),
+        }
+    }
+    if upcoming.is_empty() {
+        "Valid cron expression, but it never fires".to_string()
+    } else {
+        let description = scheduler::describe_cron(expression).unwrap_or_default();
+        format!("{description}. Next runs: {}", upcoming.join(", "))
+    }
+}
+
+fn is_daemon_paused(paths: &AppPaths, daemon_pid: Option<i32>) -> bool {
+    daemon_pid.is_some() && daemon::read_state(paths).map(|s| s.paused).unwrap_or(false)
+}
+
+fn load_last_status(paths: &AppPaths) -> Option<HashMap<String, String>> {
+    let state = daemon::read_state(paths).ok()?;
+    let mut out = HashMap::new();
+    for run in &state.recent_runs {
+        out.insert(run.job_id.clone(), run.status.clone());
+    }
+    for job in &state.jobs {
+        if let Some(last) = &job.last_result {
+            out.insert(job.id.clone(), last.status.clone());
+        }
+    }
+    Some(out)
 }
 
-fn job_file_path(jobs_dir: &Path, job_id: &str) -> std::path::PathBuf {
-    jobs_dir.join(format!("{job_id}.json"))
+// The daemon's view wins while it runs (it knows about paused/skipped slots); otherwise
+// compute from the job files.
+fn load_jobs(paths: &AppPaths) -> Result<(Vec<JobConfig>, Option<String>)> {
+    let loaded = config::load_jobs_skipping_collisions(paths)?;
+    let skipped = config::describe_skipped(&paths.jobs_dir, &loaded);
+    Ok((loaded.jobs, skipped))
+}
+
+pub(crate) fn load_next_runs(paths: &AppPaths, daemon_pid: Option<i32>, jobs: &[JobConfig]) -> HashMap<String, DateTime<Local>> {
+    if daemon_pid.is_some()
+        && let Ok(state) = daemon::read_state(paths)
+    {
+        return state
+            .jobs
+            .into_iter()
+            .filter_map(|job| job.next_run.map(|next| (job.id, next)))
+            .collect();
+    }
+    let now = Local::now();
+    jobs.iter()
+        .filter_map(|job| {
+            let next = scheduler::next_run_after(job, now).ok().flatten()?;
+            Some((job.id.clone(), next))
+        })
+        .collect()
+}
+
+fn load_output_tails(paths: &AppPaths) -> HashMap<String, Vec<String>> {
+    let Ok(state) = daemon::read_state(paths) else {
+        return HashMap::new();
+    };
+    state
+        .recent_runs
+        .into_iter()
+        .filter(|run| !run.output_tail.is_empty())
+        .map(|run| (run.run_id, run.output_tail))
+        .collect()
+}
+
+const OUTCOME_STRIP_LEN: usize = 10;
+
+// Oldest first, so the strip reads left to right like a timeline.
+fn load_outcomes(paths: &AppPaths) -> HashMap<String, Vec<String>> {
+    let Ok(state) = daemon::read_state(paths) else {
+        return HashMap::new();
+    };
+    let mut out: HashMap<String, Vec<String>> = HashMap::new();
+    for run in state.recent_runs {
+        if matches!(run.status.as_str(), "success" | "failed" | "timeout") {
+            out.entry(run.job_id).or_default().push(run.status);
+        }
+    }
+    for statuses in out.values_mut() {
+        let excess = statuses.len().saturating_sub(OUTCOME_STRIP_LEN);
+        statuses.drain(..excess);
+    }
+    out
 }
 
 fn load_history_runs(logs_dir: &Path) -> Result<Vec<String>> {
@@ -1662,7 +2832,7 @@ fn load_history_runs(logs_dir: &Path) -> Result<Vec<String>> {
     let file = fs::File::open(latest)?;
     let reader = BufReader::new(file);
     let mut lines: Vec<String> = reader.lines().collect::<std::result::Result<Vec<_>, _>>()?;
-    let start = lines.len().saturating_sub(100);
+    let start = lines.len().saturating_sub(daemon::history_size());
     lines = lines[start..].to_vec();
     lines.reverse();
     Ok(lines)
@@ -0,0 +1,98 @@
+use nix::sys::signal::{Signal, kill};
+use nix::unistd::Pid;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+struct Daemon {
+    child: Child,
+}
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        let _ = kill(Pid::from_raw(self.child.id() as i32), Signal::SIGINT);
+        let _ = self.child.wait();
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("macrond-test-{name}-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+fn write_config(path: &Path, job_ids: &[&str]) {
+    let jobs: Vec<_> = job_ids
+        .iter()
+        .map(|id| {
+            json!({
+                "id": id,
+                "name": id,
+                "schedule": { "type": "cron", "expression": "0 0 9 1 1 *" },
+                "command": { "program": "true" }
+            })
+        })
+        .collect();
+    // Replaced by rename, the way editors and config mounts update the file.
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, serde_json::to_vec(&json!({ "jobs": jobs })).unwrap()).unwrap();
+    std::fs::rename(&tmp, path).unwrap();
+}
+
+fn daemon_log(base_dir: &Path) -> String {
+    let mut log = String::new();
+    for entry in std::fs::read_dir(base_dir.join("logs")).into_iter().flatten().flatten() {
+        if entry.file_name().to_string_lossy().starts_with("daemon-") {
+            log.push_str(&std::fs::read_to_string(entry.path()).unwrap_or_default());
+        }
+    }
+    log
+}
+
+fn wait_for_log(base_dir: &Path, needle: &str, count: usize) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        if daemon_log(base_dir).matches(needle).count() >= count {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
+
+#[test]
+fn config_mode_reloads_only_for_the_config_file() {
+    let base_dir = temp_dir("base");
+    let config_dir = temp_dir("config");
+    let config = config_dir.join("jobs.json");
+    write_config(&config, &["a"]);
+
+    let daemon = Daemon {
+        child: Command::new(env!("CARGO_BIN_EXE_macrond"))
+            .arg("--base-dir")
+            .arg(&base_dir)
+            .arg("--config")
+            .arg(&config)
+            .args(["daemon", "--tick-ms", "50"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("spawn daemon"),
+    };
+    assert!(wait_for_log(&base_dir, "daemon started", 1), "daemon did not start");
+
+    std::fs::write(config_dir.join("notes.txt"), "unrelated").unwrap();
+    std::fs::write(config_dir.join("other.json"), "{}").unwrap();
+    std::thread::sleep(Duration::from_millis(1500));
+    assert!(!daemon_log(&base_dir).contains("jobs reloaded"), "sibling files triggered a reload");
+
+    write_config(&config, &["a", "b"]);
+    assert!(wait_for_log(&base_dir, "jobs reloaded files=2", 1), "config change was not picked up");
+    assert_eq!(daemon_log(&base_dir).matches("jobs reloaded").count(), 1);
+
+    drop(daemon);
+    let _ = std::fs::remove_dir_all(&base_dir);
+    let _ = std::fs::remove_dir_all(&config_dir);
+}
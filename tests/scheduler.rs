@@ -0,0 +1,355 @@
+use chrono::{DateTime, Local, LocalResult, NaiveDateTime, TimeZone};
+use macrond::model::JobConfig;
+use macrond::scheduler::next_run_after;
+use serde_json::{Value, json};
+use std::sync::Once;
+
+static TZ: Once = Once::new();
+
+// All cases are written against America/New_York so DST transitions are known:
+// 2024-03-10 02:00 -> 03:00 (spring forward), 2024-11-03 02:00 -> 01:00 (fall back).
+fn new_york() {
+    TZ.call_once(|| {
+        // SAFETY: runs once before any test in this binary reads the local time zone.
+        unsafe { std::env::set_var("TZ", "America/New_York") };
+    });
+}
+
+fn local(value: &str) -> DateTime<Local> {
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").expect("valid test time");
+    match Local.from_local_datetime(&naive) {
+        LocalResult::Single(v) => v,
+        LocalResult::Ambiguous(a, b) => a.min(b),
+        LocalResult::None => panic!("{value} does not exist locally"),
+    }
+}
+
+fn job(schedule: Value) -> JobConfig {
+    serde_json::from_value(json!({
+        "id": "t",
+        "name": "t",
+        "schedule": schedule,
+        "command": { "program": "true" }
+    }))
+    .expect("valid job")
+}
+
+fn check(cases: &[(Value, &str, Option<&str>)]) {
+    new_york();
+    for (schedule, after, expected) in cases {
+        let got = next_run_after(&job(schedule.clone()), local(after)).expect("next run");
+        let expected = expected.map(|v| DateTime::parse_from_rfc3339(v).expect("valid expected"));
+        assert_eq!(
+            got.map(|v| v.fixed_offset()),
+            expected,
+            "schedule={schedule} after={after}"
+        );
+    }
+}
+
+fn simple(repeat: &str, extra: Value) -> Value {
+    let mut value = json!({ "type": "simple", "repeat": repeat });
+    value
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    value
+}
+
+#[test]
+fn daily() {
+    let at_8 = simple("daily", json!({ "time": "08:00" }));
+    let twice = simple("daily", json!({ "times": ["20:00", "08:00"] }));
+    let midnight = simple("daily", json!({ "time": "00:00" }));
+    check(&[
+        (
+            at_8.clone(),
+            "2024-01-15 07:59:59",
+            Some("2024-01-15T08:00:00-05:00"),
+        ),
+        (
+            at_8.clone(),
+            "2024-01-15 08:00:00",
+            Some("2024-01-16T08:00:00-05:00"),
+        ),
+        (
+            at_8.clone(),
+            "2024-01-31 09:00:00",
+            Some("2024-02-01T08:00:00-05:00"),
+        ),
+        (
+            at_8.clone(),
+            "2024-12-31 23:59:59",
+            Some("2025-01-01T08:00:00-05:00"),
+        ),
+        (
+            midnight.clone(),
+            "2024-01-15 23:59:30",
+            Some("2024-01-16T00:00:00-05:00"),
+        ),
+        (
+            midnight,
+            "2024-01-16 00:00:00",
+            Some("2024-01-17T00:00:00-05:00"),
+        ),
+        (
+            twice.clone(),
+            "2024-01-15 12:00:00",
+            Some("2024-01-15T20:00:00-05:00"),
+        ),
+        (
+            twice,
+            "2024-01-15 21:00:00",
+            Some("2024-01-16T08:00:00-05:00"),
+        ),
+    ]);
+}
+
+#[test]
+fn weekly() {
+    let friday = simple("weekly", json!({ "weekday": "fri", "time": "09:00" }));
+    let monday = simple("weekly", json!({ "weekday": 1, "time": "09:00" }));
+    let sunday = simple("weekly", json!({ "weekday": 7, "time": "09:00" }));
+    check(&[
+        (
+            friday.clone(),
+            "2024-01-19 08:59:00",
+            Some("2024-01-19T09:00:00-05:00"),
+        ),
+        (
+            friday,
+            "2024-01-19 09:00:00",
+            Some("2024-01-26T09:00:00-05:00"),
+        ),
+        (
+            monday,
+            "2024-01-21 10:00:00",
+            Some("2024-01-22T09:00:00-05:00"),
+        ),
+        (
+            sunday,
+            "2024-12-30 00:00:00",
+            Some("2025-01-05T09:00:00-05:00"),
+        ),
+    ]);
+}
+
+#[test]
+fn monthly() {
+    let day_31 = simple("monthly", json!({ "day": 31, "time": "10:00" }));
+    let day_29 = simple("monthly", json!({ "day": 29, "time": "10:00" }));
+    let last = simple("monthly", json!({ "day": "last", "time": "10:00" }));
+    let day_15 = simple("monthly", json!({ "day": 15, "time": "10:00" }));
+    check(&[
+        (
+            day_31.clone(),
+            "2024-04-01 00:00:00",
+            Some("2024-04-30T10:00:00-04:00"),
+        ),
+        (
+            day_31,
+            "2024-01-31 10:00:00",
+            Some("2024-02-29T10:00:00-05:00"),
+        ),
+        (
+            day_29,
+            "2023-01-30 00:00:00",
+            Some("2023-02-28T10:00:00-05:00"),
+        ),
+        (
+            last.clone(),
+            "2024-02-01 00:00:00",
+            Some("2024-02-29T10:00:00-05:00"),
+        ),
+        (
+            last,
+            "2024-02-29 10:00:00",
+            Some("2024-03-31T10:00:00-04:00"),
+        ),
+        (
+            day_15,
+            "2024-12-20 00:00:00",
+            Some("2025-01-15T10:00:00-05:00"),
+        ),
+    ]);
+}
+
+#[test]
+fn yearly() {
+    let leap_day = simple("yearly", json!({ "month": 2, "day": 29, "time": "10:00" }));
+    check(&[
+        (
+            leap_day.clone(),
+            "2024-01-01 00:00:00",
+            Some("2024-02-29T10:00:00-05:00"),
+        ),
+        (
+            leap_day.clone(),
+            "2024-03-01 00:00:00",
+            Some("2025-02-28T10:00:00-05:00"),
+        ),
+        (
+            leap_day,
+            "2027-03-01 00:00:00",
+            Some("2028-02-29T10:00:00-05:00"),
+        ),
+    ]);
+}
+
+#[test]
+fn hourly_and_every_minute() {
+    let half_past = simple("hourly", json!({ "minute": 30 }));
+    let every_minute = simple("everyminute", json!({}));
+    check(&[
+        (
+            half_past.clone(),
+            "2024-01-15 10:29:59",
+            Some("2024-01-15T10:30:00-05:00"),
+        ),
+        (
+            half_past.clone(),
+            "2024-01-15 10:30:00",
+            Some("2024-01-15T11:30:00-05:00"),
+        ),
+        (
+            half_past,
+            "2024-01-15 23:45:00",
+            Some("2024-01-16T00:30:00-05:00"),
+        ),
+        (
+            every_minute.clone(),
+            "2024-01-15 10:00:00",
+            Some("2024-01-15T10:01:00-05:00"),
+        ),
+        (
+            every_minute,
+            "2024-01-31 23:59:30",
+            Some("2024-02-01T00:00:00-05:00"),
+        ),
+    ]);
+}
+
+#[test]
+fn once() {
+    let local_once = simple("once", json!({ "once_at": "2024-05-01 12:00" }));
+    let utc_once = simple("once", json!({ "once_at": "2024-05-01T12:00:00Z" }));
+    check(&[
+        (
+            local_once.clone(),
+            "2024-04-30 00:00:00",
+            Some("2024-05-01T12:00:00-04:00"),
+        ),
+        (local_once, "2024-05-01 12:00:00", None),
+        (
+            utc_once,
+            "2024-04-30 00:00:00",
+            Some("2024-05-01T08:00:00-04:00"),
+        ),
+    ]);
+}
+
+#[test]
+fn cron_uses_local_time() {
+    let weekdays = json!({ "type": "cron", "expression": "0 0 9 * * Mon-Fri" });
+    check(&[
+        (
+            weekdays.clone(),
+            "2024-01-19 08:00:00",
+            Some("2024-01-19T09:00:00-05:00"),
+        ),
+        (
+            weekdays,
+            "2024-01-19 09:00:00",
+            Some("2024-01-22T09:00:00-05:00"),
+        ),
+    ]);
+}
+
+#[test]
+fn dst_transitions() {
+    let at_8 = simple("daily", json!({ "time": "08:00" }));
+    let at_0130 = simple("daily", json!({ "time": "01:30" }));
+    let half_past = simple("hourly", json!({ "minute": 30 }));
+    let every_minute = simple("everyminute", json!({}));
+    check(&[
+        (
+            at_8,
+            "2024-03-09 09:00:00",
+            Some("2024-03-10T08:00:00-04:00"),
+        ),
+        (
+            at_0130,
+            "2024-11-03 00:00:00",
+            Some("2024-11-03T01:30:00-04:00"),
+        ),
+        (
+            half_past.clone(),
+            "2024-03-10 01:45:00",
+            Some("2024-03-10T03:30:00-04:00"),
+        ),
+        (
+            every_minute,
+            "2024-03-10 01:59:30",
+            Some("2024-03-10T03:00:00-04:00"),
+        ),
+    ]);
+    new_york();
+    let fall_back_second_0145 = DateTime::parse_from_rfc3339("2024-11-03T01:45:00-05:00").unwrap();
+    let got = next_run_after(&job(half_past), fall_back_second_0145.with_timezone(&Local)).unwrap();
+    assert_eq!(
+        got.map(|v| v.fixed_offset()),
+        Some(DateTime::parse_from_rfc3339("2024-11-03T02:30:00-05:00").unwrap())
+    );
+}
+
+#[test]
+fn dst_gap() {
+    let shift = simple("daily", json!({ "time": "02:30" }));
+    let skip = simple("daily", json!({ "time": "02:30", "dst_gap": "skip" }));
+    let weekly_skip = simple(
+        "weekly",
+        json!({ "weekday": 7, "time": "02:30", "dst_gap": "skip" }),
+    );
+    let monthly_skip = simple(
+        "monthly",
+        json!({ "day": 10, "time": "02:30", "dst_gap": "skip" }),
+    );
+    let once_shift = simple("once", json!({ "once_at": "2024-03-10 02:30" }));
+    let once_skip = simple(
+        "once",
+        json!({ "once_at": "2024-03-10 02:30", "dst_gap": "skip" }),
+    );
+    check(&[
+        (
+            shift.clone(),
+            "2024-03-10 00:00:00",
+            Some("2024-03-10T03:00:00-04:00"),
+        ),
+        (
+            shift,
+            "2024-03-10 03:00:00",
+            Some("2024-03-11T02:30:00-04:00"),
+        ),
+        (
+            skip,
+            "2024-03-10 00:00:00",
+            Some("2024-03-11T02:30:00-04:00"),
+        ),
+        (
+            weekly_skip,
+            "2024-03-09 00:00:00",
+            Some("2024-03-17T02:30:00-04:00"),
+        ),
+        (
+            monthly_skip,
+            "2024-03-01 00:00:00",
+            Some("2024-04-10T02:30:00-04:00"),
+        ),
+        (
+            once_shift,
+            "2024-03-01 00:00:00",
+            Some("2024-03-10T03:00:00-04:00"),
+        ),
+        (once_skip, "2024-03-01 00:00:00", None),
+    ]);
+}